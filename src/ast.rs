@@ -1,35 +1,59 @@
-use crate::token::Token;
+#[cfg(feature = "bigint")]
+use num_bigint::BigInt;
+
+use crate::token::{Span, Token};
 
 pub trait Node {
     fn token_literal(&self) -> String;
 
     fn string(&self) -> String;
+
+    // The region of the source this node was parsed from.
+    fn span(&self) -> Span;
 }
 
 #[derive(Debug)]
 pub struct Program {
     // This is a vector of objects implementing the Statement trait.
     pub statements: Vec<StatementVariant>,
+    pub span: Span,
 }
 
 // The program is the root node.
 impl Node for Program {
     fn token_literal(&self) -> String {
-        if self.statements.len() > 0 {
+        if !self.statements.is_empty() {
             self.statements[0].token_literal()
         } else {
             " ".to_string()
         }
     }
 
+    fn span(&self) -> Span {
+        self.span
+    }
+
     fn string(&self) -> String {
-        let mut out = String::from("");
-        for statement in &self.statements {
-            out.push_str(&statement.string());
+        join_statements(&self.statements, "")
+    }
+}
+
+// Expression statements print without their `;`, so one is put back
+// between them and whatever follows, or `a; b` would read as `ab`.
+fn join_statements(statements: &[StatementVariant], separator: &str) -> String {
+    let mut out = String::from("");
+    for (i, statement) in statements.iter().enumerate() {
+        if i > 0 {
+            out.push_str(separator);
         }
+        out.push_str(&statement.string());
 
-        out
+        if matches!(statement, StatementVariant::Expression(_)) && i + 1 < statements.len() {
+            out.push(';');
+        }
     }
+
+    out
 }
 
 ///////////////////////
@@ -63,12 +87,21 @@ impl Node for StatementVariant {
             StatementVariant::Expression(s) => s.string(),
         }
     }
+
+    fn span(&self) -> Span {
+        match self {
+            StatementVariant::Let(s) => s.span,
+            StatementVariant::Return(s) => s.span,
+            StatementVariant::Expression(s) => s.span,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct BlockStatement {
     pub token: Token, // the { token
     pub statements: Vec<StatementVariant>,
+    pub span: Span,
 }
 
 impl Node for BlockStatement {
@@ -76,14 +109,16 @@ impl Node for BlockStatement {
         self.token.literal.clone()
     }
 
-    fn string(&self) -> String {
-        let mut out = String::new();
+    fn span(&self) -> Span {
+        self.span
+    }
 
-        for statement in self.statements.iter() {
-            out.push_str(&statement.string());
+    fn string(&self) -> String {
+        if self.statements.is_empty() {
+            return "{}".to_string();
         }
 
-        out
+        format!("{{ {} }}", join_statements(&self.statements, " "))
     }
 }
 
@@ -92,6 +127,9 @@ pub struct LetStatement {
     pub token: Token,
     pub name: Identifier,
     pub value: ExpressionVariant,
+    // Text of the `///` comments written right above the statement.
+    pub doc: Option<String>,
+    pub span: Span,
 }
 
 impl Statement for LetStatement {
@@ -105,6 +143,10 @@ impl Node for LetStatement {
         self.token.literal.clone()
     }
 
+    fn span(&self) -> Span {
+        self.span
+    }
+
     fn string(&self) -> String {
         let mut out = String::from("");
 
@@ -114,10 +156,8 @@ impl Node for LetStatement {
             &self.name.string()
         ));
 
-        if let Some(expr_var) = &self.value {
-            if let ExpressionVariants::Ident(ident) = expr_var {
-                out.push_str(&ident.string());
-            }
+        if let Some(value) = &self.value {
+            out.push_str(&value.string());
         }
 
         out.push(';');
@@ -130,6 +170,7 @@ impl Node for LetStatement {
 pub struct ReturnStatement {
     pub token: Token,
     pub return_value: ExpressionVariant,
+    pub span: Span,
 }
 
 impl Statement for ReturnStatement {
@@ -143,15 +184,17 @@ impl Node for ReturnStatement {
         self.token.literal.clone()
     }
 
+    fn span(&self) -> Span {
+        self.span
+    }
+
     fn string(&self) -> String {
         let mut out = String::from("");
 
-        out.push_str(&format!("{} ", &self.token_literal()));
+        out.push_str(&self.token_literal());
 
-        if let Some(expr_var) = &self.return_value {
-            if let ExpressionVariants::Ident(ident) = expr_var {
-                out.push_str(&ident.string());
-            }
+        if let Some(return_value) = &self.return_value {
+            out.push_str(&format!(" {}", return_value.string()));
         }
         out.push(';');
 
@@ -163,6 +206,7 @@ impl Node for ReturnStatement {
 pub struct ExpressionStatement {
     pub token: Token,
     pub expression: ExpressionVariant,
+    pub span: Span,
 }
 
 impl Node for ExpressionStatement {
@@ -170,6 +214,10 @@ impl Node for ExpressionStatement {
         self.token.literal.clone()
     }
 
+    fn span(&self) -> Span {
+        self.span
+    }
+
     fn string(&self) -> String {
         match &self.expression {
             Some(exp) => exp.string(),
@@ -198,6 +246,7 @@ pub struct InfixExpression {
     pub left: Box<ExpressionVariants>,
     pub operator: String,
     pub right: Box<ExpressionVariants>,
+    pub span: Span,
 }
 
 impl Node for InfixExpression {
@@ -205,6 +254,10 @@ impl Node for InfixExpression {
         self.token.literal.clone()
     }
 
+    fn span(&self) -> Span {
+        self.span
+    }
+
     fn string(&self) -> String {
         format!(
             "({} {} {})",
@@ -224,6 +277,7 @@ pub struct PrefixExpression {
     pub token: Token,
     pub operator: String,
     pub right: Box<ExpressionVariants>, //?
+    pub span: Span,
 }
 
 impl Node for PrefixExpression {
@@ -231,6 +285,10 @@ impl Node for PrefixExpression {
         self.token.literal.clone()
     }
 
+    fn span(&self) -> Span {
+        self.span
+    }
+
     fn string(&self) -> String {
         format!("({}{})", self.operator, self.right.string())
     }
@@ -244,6 +302,7 @@ impl Expression for PrefixExpression {
 pub struct Identifier {
     pub token: Token,
     pub value: String,
+    pub span: Span,
 }
 
 impl Expression for Identifier {
@@ -255,6 +314,10 @@ impl Node for Identifier {
         self.token.literal.clone()
     }
 
+    fn span(&self) -> Span {
+        self.span
+    }
+
     fn string(&self) -> String {
         self.value.clone()
     }
@@ -264,6 +327,7 @@ impl Node for Identifier {
 pub struct IntegerLiteral {
     pub token: Token,
     pub value: i64,
+    pub span: Span,
 }
 
 impl Expression for IntegerLiteral {
@@ -277,15 +341,109 @@ impl Node for IntegerLiteral {
         self.token.literal.clone()
     }
 
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn string(&self) -> String {
+        self.token_literal()
+    }
+}
+
+// An integer literal too large for an i64.
+#[cfg(feature = "bigint")]
+#[derive(Debug, Clone)]
+pub struct BigIntegerLiteral {
+    pub token: Token,
+    pub value: BigInt,
+    pub span: Span,
+}
+
+#[cfg(feature = "bigint")]
+impl Node for BigIntegerLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
     fn string(&self) -> String {
         self.token_literal()
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct FloatLiteral {
+    pub token: Token,
+    pub value: f64,
+    pub span: Span,
+}
+
+impl Node for FloatLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    // Floats are printed as written, so `1e-9` stays `1e-9`.
+    fn string(&self) -> String {
+        self.token_literal()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StringLiteral {
+    pub token: Token,
+    pub value: String,
+    pub span: Span,
+}
+
+impl Node for StringLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn string(&self) -> String {
+        quote_string(&self.value)
+    }
+}
+
+// Writes a string back out as a Mono string literal, escaping
+// whatever the lexer would otherwise read differently.
+pub fn quote_string(value: &str) -> String {
+    let mut out = String::from("\"");
+
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            ch if ch.is_control() => out.push_str(&format!("\\u{{{:x}}}", ch as u32)),
+            ch => out.push(ch),
+        }
+    }
+
+    out.push('"');
+
+    out
+}
+
 #[derive(Debug, Clone)]
 pub struct Boolean {
     pub token: Token,
     pub value: bool,
+    pub span: Span,
 }
 
 impl Expression for Boolean {
@@ -299,6 +457,10 @@ impl Node for Boolean {
         self.token.literal.clone()
     }
 
+    fn span(&self) -> Span {
+        self.span
+    }
+
     fn string(&self) -> String {
         self.token.literal.clone()
     }
@@ -310,6 +472,7 @@ pub struct IfExpression {
     pub condition: Box<ExpressionVariants>,
     pub consequence: BlockStatement,
     pub alternative: Option<BlockStatement>,
+    pub span: Span,
 }
 
 impl Node for IfExpression {
@@ -317,29 +480,167 @@ impl Node for IfExpression {
         self.token.literal.clone()
     }
 
+    fn span(&self) -> Span {
+        self.span
+    }
+
     fn string(&self) -> String {
-        let mut out = String::from(format!(
-            "if{} {}",
+        let mut out = format!(
+            "if ({}) {}",
             self.condition.string(),
             self.consequence.string()
-        ));
+        );
 
         if let Some(alternative) = &self.alternative {
-            out.push_str(&format!("else {}", alternative.string()));
+            out.push_str(&format!(" else {}", alternative.string()));
         }
 
         out
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct FunctionLiteral {
+    pub token: Token, // the fn token
+    pub parameters: Vec<Identifier>,
+    pub body: BlockStatement,
+    pub span: Span,
+}
+
+impl Node for FunctionLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn string(&self) -> String {
+        let parameters: Vec<String> = self.parameters.iter().map(|p| p.string()).collect();
+
+        format!(
+            "{}({}) {}",
+            self.token_literal(),
+            parameters.join(", "),
+            self.body.string()
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CallExpression {
+    pub token: Token,                      // the ( token
+    pub function: Box<ExpressionVariants>, // Identifier or FunctionLiteral
+    pub arguments: Vec<ExpressionVariants>,
+    pub span: Span,
+}
+
+impl Node for CallExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn string(&self) -> String {
+        let arguments: Vec<String> = self.arguments.iter().map(|a| a.string()).collect();
+
+        format!("{}({})", self.function.string(), arguments.join(", "))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ArrayLiteral {
+    pub token: Token, // the [ token
+    pub elements: Vec<ExpressionVariants>,
+    pub span: Span,
+}
+
+impl Node for ArrayLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn string(&self) -> String {
+        let elements: Vec<String> = self.elements.iter().map(|e| e.string()).collect();
+
+        format!("[{}]", elements.join(", "))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IndexExpression {
+    pub token: Token, // the [ token
+    pub left: Box<ExpressionVariants>,
+    pub index: Box<ExpressionVariants>,
+    pub span: Span,
+}
+
+impl Node for IndexExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn string(&self) -> String {
+        format!("({}[{}])", self.left.string(), self.index.string())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HashLiteral {
+    pub token: Token, // the { token
+    pub pairs: Vec<(ExpressionVariants, ExpressionVariants)>,
+    pub span: Span,
+}
+
+impl Node for HashLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn string(&self) -> String {
+        let pairs: Vec<String> = self
+            .pairs
+            .iter()
+            .map(|(key, value)| format!("{}: {}", key.string(), value.string()))
+            .collect();
+
+        format!("{{{}}}", pairs.join(", "))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum ExpressionVariants {
     Ident(Identifier),
     Integer(IntegerLiteral),
+    #[cfg(feature = "bigint")]
+    BigInteger(BigIntegerLiteral),
+    Float(FloatLiteral),
+    String(StringLiteral),
     Prefix(PrefixExpression),
     Infix(InfixExpression),
     Boolean(Boolean),
     If(IfExpression),
+    Function(FunctionLiteral),
+    Call(CallExpression),
+    Array(ArrayLiteral),
+    Index(IndexExpression),
+    Hash(HashLiteral),
 }
 
 impl Node for ExpressionVariants {
@@ -351,12 +652,170 @@ impl Node for ExpressionVariants {
         match self {
             ExpressionVariants::Ident(ident) => ident.string(),
             ExpressionVariants::Integer(int_lit) => int_lit.string(),
+            #[cfg(feature = "bigint")]
+            ExpressionVariants::BigInteger(int_lit) => int_lit.string(),
+            ExpressionVariants::Float(float_lit) => float_lit.string(),
+            ExpressionVariants::String(str_lit) => str_lit.string(),
             ExpressionVariants::Prefix(pe) => pe.string(),
             ExpressionVariants::Infix(ie) => ie.string(),
             ExpressionVariants::Boolean(b) => b.string(),
             ExpressionVariants::If(ie) => ie.string(),
+            ExpressionVariants::Function(fl) => fl.string(),
+            ExpressionVariants::Call(ce) => ce.string(),
+            ExpressionVariants::Array(al) => al.string(),
+            ExpressionVariants::Index(ie) => ie.string(),
+            ExpressionVariants::Hash(hl) => hl.string(),
+        }
+    }
+
+    fn span(&self) -> Span {
+        match self {
+            ExpressionVariants::Ident(ident) => ident.span,
+            ExpressionVariants::Integer(int_lit) => int_lit.span,
+            #[cfg(feature = "bigint")]
+            ExpressionVariants::BigInteger(int_lit) => int_lit.span,
+            ExpressionVariants::Float(float_lit) => float_lit.span,
+            ExpressionVariants::String(str_lit) => str_lit.span,
+            ExpressionVariants::Prefix(pe) => pe.span,
+            ExpressionVariants::Infix(ie) => ie.span,
+            ExpressionVariants::Boolean(b) => b.span,
+            ExpressionVariants::If(ie) => ie.span,
+            ExpressionVariants::Function(fl) => fl.span,
+            ExpressionVariants::Call(ce) => ce.span,
+            ExpressionVariants::Array(al) => al.span,
+            ExpressionVariants::Index(ie) => ie.span,
+            ExpressionVariants::Hash(hl) => hl.span,
         }
     }
 }
 
 pub type ExpressionVariant = Option<ExpressionVariants>;
+
+// An indented outline of a program, one node per line along with where
+// it starts, for looking at what the parser produced.
+pub fn tree(program: &Program) -> String {
+    let mut out = String::new();
+
+    for statement in program.statements.iter() {
+        statement_tree(&mut out, statement, 0);
+    }
+
+    out
+}
+
+fn tree_line(out: &mut String, depth: usize, label: &str, span: Span) {
+    out.push_str(&format!("{}{} @ {}\n", "  ".repeat(depth), label, span));
+}
+
+fn statement_tree(out: &mut String, statement: &StatementVariant, depth: usize) {
+    match statement {
+        StatementVariant::Let(ls) => {
+            tree_line(out, depth, &format!("Let {}", ls.name.value), ls.span);
+            if let Some(value) = &ls.value {
+                expression_tree(out, value, depth + 1);
+            }
+        }
+        StatementVariant::Return(rs) => {
+            tree_line(out, depth, "Return", rs.span);
+            if let Some(value) = &rs.return_value {
+                expression_tree(out, value, depth + 1);
+            }
+        }
+        StatementVariant::Expression(es) => {
+            if let Some(expression) = &es.expression {
+                expression_tree(out, expression, depth);
+            }
+        }
+    }
+}
+
+fn block_tree(out: &mut String, label: &str, block: &BlockStatement, depth: usize) {
+    tree_line(out, depth, label, block.span);
+
+    for statement in block.statements.iter() {
+        statement_tree(out, statement, depth + 1);
+    }
+}
+
+fn expression_tree(out: &mut String, expression: &ExpressionVariants, depth: usize) {
+    let span = expression.span();
+
+    match expression {
+        ExpressionVariants::Ident(ident) => {
+            tree_line(out, depth, &format!("Identifier {}", ident.value), span)
+        }
+        ExpressionVariants::Integer(int_lit) => {
+            tree_line(out, depth, &format!("Integer {}", int_lit.value), span)
+        }
+        #[cfg(feature = "bigint")]
+        ExpressionVariants::BigInteger(int_lit) => {
+            tree_line(out, depth, &format!("Integer {}", int_lit.value), span)
+        }
+        ExpressionVariants::Float(float_lit) => {
+            tree_line(out, depth, &format!("Float {:?}", float_lit.value), span)
+        }
+        ExpressionVariants::String(str_lit) => tree_line(
+            out,
+            depth,
+            &format!("String {}", quote_string(&str_lit.value)),
+            span,
+        ),
+        ExpressionVariants::Boolean(b) => {
+            tree_line(out, depth, &format!("Boolean {}", b.value), span)
+        }
+        ExpressionVariants::Prefix(pe) => {
+            tree_line(out, depth, &format!("Prefix {}", pe.operator), span);
+            expression_tree(out, &pe.right, depth + 1);
+        }
+        ExpressionVariants::Infix(ie) => {
+            tree_line(out, depth, &format!("Infix {}", ie.operator), span);
+            expression_tree(out, &ie.left, depth + 1);
+            expression_tree(out, &ie.right, depth + 1);
+        }
+        ExpressionVariants::If(ie) => {
+            tree_line(out, depth, "If", span);
+            expression_tree(out, &ie.condition, depth + 1);
+            block_tree(out, "Then", &ie.consequence, depth + 1);
+            if let Some(alternative) = &ie.alternative {
+                block_tree(out, "Else", alternative, depth + 1);
+            }
+        }
+        ExpressionVariants::Function(fl) => {
+            let parameters: Vec<&str> = fl.parameters.iter().map(|p| p.value.as_str()).collect();
+
+            tree_line(
+                out,
+                depth,
+                &format!("Function({})", parameters.join(", ")),
+                span,
+            );
+            block_tree(out, "Body", &fl.body, depth + 1);
+        }
+        ExpressionVariants::Call(ce) => {
+            tree_line(out, depth, "Call", span);
+            expression_tree(out, &ce.function, depth + 1);
+            for argument in ce.arguments.iter() {
+                expression_tree(out, argument, depth + 1);
+            }
+        }
+        ExpressionVariants::Array(al) => {
+            tree_line(out, depth, "Array", span);
+            for element in al.elements.iter() {
+                expression_tree(out, element, depth + 1);
+            }
+        }
+        ExpressionVariants::Index(ie) => {
+            tree_line(out, depth, "Index", span);
+            expression_tree(out, &ie.left, depth + 1);
+            expression_tree(out, &ie.index, depth + 1);
+        }
+        ExpressionVariants::Hash(hl) => {
+            tree_line(out, depth, "Hash", span);
+            for (key, value) in hl.pairs.iter() {
+                tree_line(out, depth + 1, "Pair", key.span().to(value.span()));
+                expression_tree(out, key, depth + 2);
+                expression_tree(out, value, depth + 2);
+            }
+        }
+    }
+}
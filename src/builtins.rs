@@ -0,0 +1,190 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard, OnceLock},
+};
+
+use crate::object::{Arity, Builtin, BuiltinFunction, Object};
+
+static REGISTRY: OnceLock<Mutex<HashMap<String, Builtin>>> = OnceLock::new();
+
+fn registry() -> MutexGuard<'static, HashMap<String, Builtin>> {
+    REGISTRY
+        .get_or_init(|| Mutex::new(defaults()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn defaults() -> HashMap<String, Builtin> {
+    let builtins: Vec<(&str, Arity, BuiltinFunction)> = vec![
+        ("len", Arity::Exact(1), len),
+        ("first", Arity::Exact(1), first),
+        ("last", Arity::Exact(1), last),
+        ("rest", Arity::Exact(1), rest),
+        ("push", Arity::Exact(2), push),
+        ("puts", Arity::Any, puts),
+        ("type", Arity::Exact(1), type_of),
+        ("str", Arity::Exact(1), str),
+        ("int", Arity::Exact(1), int),
+        ("float", Arity::Exact(1), float),
+    ];
+
+    builtins
+        .into_iter()
+        .map(|(name, arity, function)| {
+            let builtin = Builtin {
+                name: name.to_string(),
+                arity,
+                function,
+            };
+
+            (name.to_string(), builtin)
+        })
+        .collect()
+}
+
+// Makes a native function callable from Mono under `name`, replacing
+// any builtin already registered with that name. The arity is checked
+// by the evaluator before `function` is called.
+pub fn register_builtin(name: &str, arity: Arity, function: BuiltinFunction) {
+    registry().insert(
+        name.to_string(),
+        Builtin {
+            name: name.to_string(),
+            arity,
+            function,
+        },
+    );
+}
+
+// The names of every registered builtin, sorted.
+pub fn names() -> Vec<String> {
+    let mut names: Vec<String> = registry().keys().cloned().collect();
+    names.sort();
+
+    names
+}
+
+// Looks up a native function by name. Bindings in the environment
+// take precedence, so builtins can be shadowed by user code.
+pub fn lookup(name: &str) -> Option<Object> {
+    registry().get(name).cloned().map(Object::Builtin)
+}
+
+fn len(arguments: &[Object]) -> Result<Object, String> {
+    match &arguments[0] {
+        Object::String(value) => Ok(Object::Integer(value.chars().count() as i64)),
+        Object::Array(elements) => Ok(Object::Integer(elements.len() as i64)),
+        Object::Hash(pairs) => Ok(Object::Integer(pairs.len() as i64)),
+        other => Err(unsupported_argument("len", other)),
+    }
+}
+
+fn first(arguments: &[Object]) -> Result<Object, String> {
+    match &arguments[0] {
+        Object::Array(elements) => Ok(elements.first().cloned().unwrap_or(Object::Null)),
+        other => Err(unsupported_argument("first", other)),
+    }
+}
+
+fn last(arguments: &[Object]) -> Result<Object, String> {
+    match &arguments[0] {
+        Object::Array(elements) => Ok(elements.last().cloned().unwrap_or(Object::Null)),
+        other => Err(unsupported_argument("last", other)),
+    }
+}
+
+// Returns a new array holding everything but the first element.
+fn rest(arguments: &[Object]) -> Result<Object, String> {
+    match &arguments[0] {
+        Object::Array(elements) if elements.is_empty() => Ok(Object::Null),
+        Object::Array(elements) => Ok(Object::Array(elements[1..].to_vec())),
+        other => Err(unsupported_argument("rest", other)),
+    }
+}
+
+// Arrays are immutable, so `push` returns a copy with the new element.
+fn push(arguments: &[Object]) -> Result<Object, String> {
+    match &arguments[0] {
+        Object::Array(elements) => {
+            let mut elements = elements.clone();
+            elements.push(arguments[1].clone());
+
+            Ok(Object::Array(elements))
+        }
+        other => Err(unsupported_argument("push", other)),
+    }
+}
+
+fn puts(arguments: &[Object]) -> Result<Object, String> {
+    for argument in arguments {
+        println!("{}", display(argument));
+    }
+
+    Ok(Object::Null)
+}
+
+fn type_of(arguments: &[Object]) -> Result<Object, String> {
+    Ok(Object::String(arguments[0].type_name().to_string()))
+}
+
+fn str(arguments: &[Object]) -> Result<Object, String> {
+    Ok(Object::String(display(&arguments[0])))
+}
+
+fn int(arguments: &[Object]) -> Result<Object, String> {
+    match &arguments[0] {
+        Object::Integer(value) => Ok(Object::Integer(*value)),
+        // Floats are truncated towards zero. `i64::MAX as f64` rounds up
+        // to 2^63, which is out of range, so the upper bound is exclusive.
+        Object::Float(value) if (i64::MIN as f64..-(i64::MIN as f64)).contains(value) => {
+            Ok(Object::Integer(value.trunc() as i64))
+        }
+        Object::Float(value) => Err(format!("could not convert {:?} to INTEGER", value)),
+        Object::Boolean(value) => Ok(Object::Integer(*value as i64)),
+        #[cfg(feature = "bigint")]
+        Object::BigInteger(value) => Ok(Object::BigInteger(value.clone())),
+        #[cfg(feature = "bigint")]
+        Object::String(value) => match value.trim().parse::<num_bigint::BigInt>() {
+            Ok(value) => Ok(Object::big_integer(value)),
+            Err(_) => Err(format!("could not convert {:?} to INTEGER", value)),
+        },
+        #[cfg(not(feature = "bigint"))]
+        Object::String(value) => match value.trim().parse::<i64>() {
+            Ok(value) => Ok(Object::Integer(value)),
+            Err(_) => Err(format!("could not convert {:?} to INTEGER", value)),
+        },
+        other => Err(unsupported_argument("int", other)),
+    }
+}
+
+fn float(arguments: &[Object]) -> Result<Object, String> {
+    match &arguments[0] {
+        Object::Float(value) => Ok(Object::Float(*value)),
+        Object::Integer(value) => Ok(Object::Float(*value as f64)),
+        #[cfg(feature = "bigint")]
+        Object::BigInteger(value) => Ok(Object::Float(
+            num_traits::ToPrimitive::to_f64(value).unwrap_or(f64::INFINITY),
+        )),
+        Object::String(value) => match value.trim().parse::<f64>() {
+            Ok(value) => Ok(Object::Float(value)),
+            Err(_) => Err(format!("could not convert {:?} to FLOAT", value)),
+        },
+        other => Err(unsupported_argument("float", other)),
+    }
+}
+
+// Strings are written out as-is, everything else as its inspect form.
+fn display(object: &Object) -> String {
+    match object {
+        Object::String(value) => value.clone(),
+        other => other.inspect(),
+    }
+}
+
+fn unsupported_argument(name: &str, argument: &Object) -> String {
+    format!(
+        "argument to `{}` not supported, got {}",
+        name,
+        argument.type_name()
+    )
+}
@@ -0,0 +1,231 @@
+use std::fmt;
+
+use colored::{ColoredString, Colorize};
+
+use crate::{object::RuntimeError, token::Span};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl Severity {
+    fn name(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+        }
+    }
+
+    fn paint(&self, text: &str) -> ColoredString {
+        match self {
+            Severity::Error => text.bright_red().bold(),
+            Severity::Warning => text.yellow().bold(),
+            Severity::Note => text.bright_blue().bold(),
+        }
+    }
+}
+
+// A secondary span pointed at by a diagnostic, with a short explanation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+// A problem found in a Mono program, with everything needed to show
+// the user where it is and what to do about it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: &'static str,
+    pub message: String,
+    pub span: Span,
+    // Printed under the carets of the primary span.
+    pub label: Option<String>,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+    pub help: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, code: &'static str, message: String, span: Span) -> Self {
+        Self {
+            severity,
+            code,
+            message,
+            span,
+            label: None,
+            labels: Vec::new(),
+            notes: Vec::new(),
+            help: None,
+        }
+    }
+
+    pub fn error(code: &'static str, message: String, span: Span) -> Self {
+        Self::new(Severity::Error, code, message, span)
+    }
+
+    // A Mono error object raised while evaluating, so it can be shown
+    // like a parser error.
+    pub fn runtime(error: &RuntimeError) -> Self {
+        Self::error(
+            "E0100",
+            error.message.clone(),
+            error.span.unwrap_or_default(),
+        )
+    }
+
+    pub fn with_label(mut self, message: impl Into<String>) -> Self {
+        self.label = Some(message.into());
+        self
+    }
+
+    pub fn with_secondary(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.labels.push(Label {
+            span,
+            message: message.into(),
+        });
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    // Renders the diagnostic against the source it was found in, showing
+    // each line involved with the offending region underlined:
+    //
+    //   error[E0001]: Expected next token to be ASSIGN, got INT instead
+    //    --> script.mono:1:7
+    //     |
+    //   1 | let x 5;
+    //     |       ^ expected ASSIGN
+    //
+    // Colors follow the `colored` crate, so NO_COLOR is honored.
+    pub fn render(&self, name: &str, source: &str) -> String {
+        let mut markers: Vec<(Span, char, Option<&str>)> =
+            vec![(self.span, '^', self.label.as_deref())];
+        for label in self.labels.iter() {
+            markers.push((label.span, '-', Some(label.message.as_str())));
+        }
+        markers.sort_by_key(|(span, _, _)| (span.line, span.column));
+
+        let width = markers
+            .iter()
+            .map(|(span, _, _)| span.line.to_string().len())
+            .max()
+            .unwrap_or(1);
+        let gutter = " ".repeat(width);
+        let bar = "|".bright_blue().bold();
+
+        let mut out = format!(
+            "{}{}\n{}{} {}:{}\n",
+            self.severity
+                .paint(&format!("{}[{}]", self.severity.name(), self.code)),
+            format!(": {}", self.message).bold(),
+            gutter,
+            "-->".bright_blue().bold(),
+            name,
+            self.span
+        );
+        out.push_str(&format!("{} {}\n", gutter, bar));
+
+        let mut current_line = 0;
+        for (span, marker, message) in markers {
+            let (line_start, text) = line_at(source, span.start);
+
+            if span.line != current_line {
+                let number = format!("{:>width$}", span.line, width = width);
+                out.push_str(&format!(
+                    "{} {} {}\n",
+                    number.bright_blue().bold(),
+                    bar,
+                    text
+                ));
+                current_line = span.line;
+            }
+
+            // Only the part of the span on its first line is underlined.
+            let end = span.end.min(line_start + text.len()).max(span.start);
+            let padding = char_count(source, line_start, span.start);
+            let length = char_count(source, span.start, end).max(1);
+
+            let underline = marker.to_string().repeat(length);
+            let mut annotation = format!("{}{}", " ".repeat(padding), underline);
+            if let Some(message) = message {
+                annotation.push_str(&format!(" {}", message));
+            }
+
+            let annotation = if marker == '^' {
+                self.severity.paint(&annotation)
+            } else {
+                annotation.bright_blue().bold()
+            };
+            out.push_str(&format!("{} {} {}\n", gutter, bar, annotation));
+        }
+
+        for note in self.notes.iter() {
+            out.push_str(&format!(
+                "{} {} {}: {}\n",
+                gutter,
+                "=".bright_blue().bold(),
+                "note".bold(),
+                note
+            ));
+        }
+
+        if let Some(help) = &self.help {
+            out.push_str(&format!(
+                "{} {} {}: {}\n",
+                gutter,
+                "=".bright_blue().bold(),
+                "help".bold(),
+                help
+            ));
+        }
+
+        out
+    }
+}
+
+// A one line summary, used where the source is not at hand.
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.span, self.message)
+    }
+}
+
+fn char_count(source: &str, start: usize, end: usize) -> usize {
+    source
+        .get(start..end)
+        .map_or(end - start, |text| text.chars().count())
+}
+
+// Returns the byte offset where the line holding `offset` starts,
+// along with the text of that line.
+fn line_at(source: &str, offset: usize) -> (usize, &str) {
+    let bytes = source.as_bytes();
+    let offset = offset.min(bytes.len());
+    let start = bytes[..offset]
+        .iter()
+        .rposition(|b| *b == b'\n')
+        .map_or(0, |i| i + 1);
+    let end = bytes[offset..]
+        .iter()
+        .position(|b| *b == b'\n')
+        .map_or(bytes.len(), |i| offset + i);
+
+    let text = source.get(start..end).unwrap_or_default();
+
+    (start, text.trim_end_matches('\r'))
+}
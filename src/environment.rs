@@ -0,0 +1,88 @@
+use std::{cell::RefCell, collections::HashMap, fmt, rc::Rc};
+
+use crate::object::Object;
+
+// An environment is a cheap handle to a scope. Cloning it shares the
+// underlying bindings, which is what lets a function keep seeing the
+// scope it was defined in after that scope has been left.
+#[derive(Clone, Default)]
+pub struct Environment {
+    scope: Rc<RefCell<Scope>>,
+}
+
+#[derive(Default)]
+struct Scope {
+    store: HashMap<String, Object>,
+    outer: Option<Environment>,
+}
+
+impl Environment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Creates a new scope whose lookups fall back to `outer`.
+    pub fn new_enclosed(outer: &Environment) -> Self {
+        Self {
+            scope: Rc::new(RefCell::new(Scope {
+                store: HashMap::new(),
+                outer: Some(outer.clone()),
+            })),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<Object> {
+        let scope = self.scope.borrow();
+
+        match scope.store.get(name) {
+            Some(value) => Some(value.clone()),
+            None => scope.outer.as_ref().and_then(|outer| outer.get(name)),
+        }
+    }
+
+    // Bindings always go into the innermost scope, shadowing outer ones.
+    pub fn set(&mut self, name: &str, value: Object) -> Object {
+        self.scope
+            .borrow_mut()
+            .store
+            .insert(name.to_string(), value.clone());
+
+        value
+    }
+
+    // Every name visible from this scope with its value, sorted by name.
+    // Inner bindings hide outer ones of the same name.
+    pub fn bindings(&self) -> Vec<(String, Object)> {
+        let scope = self.scope.borrow();
+
+        let mut bindings = match &scope.outer {
+            Some(outer) => outer.bindings(),
+            None => Vec::new(),
+        };
+        bindings.retain(|(name, _)| !scope.store.contains_key(name));
+        bindings.extend(
+            scope
+                .store
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone())),
+        );
+        bindings.sort_by(|a, b| a.0.cmp(&b.0));
+
+        bindings
+    }
+}
+
+// Functions hold on to their environment, so printing the bindings
+// themselves could recurse forever. We only list the names.
+impl fmt::Debug for Environment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scope = self.scope.borrow();
+        let mut names: Vec<&String> = scope.store.keys().collect();
+        names.sort();
+
+        f.debug_struct("Environment")
+            .field("names", &names)
+            .field("outer", &scope.outer)
+            .finish()
+    }
+}
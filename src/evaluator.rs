@@ -0,0 +1,537 @@
+use std::{cell::Cell, collections::BTreeMap, rc::Rc};
+
+#[cfg(feature = "bigint")]
+use num_bigint::BigInt;
+#[cfg(feature = "bigint")]
+use num_traits::{Signed, ToPrimitive, Zero};
+
+use crate::{
+    ast::{
+        BlockStatement, ExpressionVariant, ExpressionVariants, HashLiteral, IfExpression, Node,
+        Program, StatementVariant,
+    },
+    builtins,
+    environment::Environment,
+    object::{Arity, Function, Object},
+};
+
+// Calls nest on the Rust stack, so runaway recursion is stopped with an
+// error before it can overflow it. Running this deep still needs a larger
+// stack than a spawned thread gets by default in debug builds.
+pub const MAX_CALL_DEPTH: usize = 1000;
+
+thread_local! {
+    // Number of user-defined functions currently being applied.
+    static CALL_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+pub fn eval(program: &Program, env: &mut Environment) -> Object {
+    let mut result = Object::Null;
+
+    for statement in program.statements.iter() {
+        result = eval_statement(statement, env);
+
+        match result {
+            // A top level return unwraps its value and stops the program.
+            Object::ReturnValue(value) => return *value,
+            Object::Error(_) => return result,
+            _ => {}
+        }
+    }
+
+    result
+}
+
+fn eval_block_statement(block: &BlockStatement, env: &mut Environment) -> Object {
+    let mut result = Object::Null;
+
+    for statement in block.statements.iter() {
+        result = eval_statement(statement, env);
+
+        // Return values are not unwrapped here so that they
+        // keep bubbling up through any enclosing blocks.
+        if matches!(result, Object::ReturnValue(_) | Object::Error(_)) {
+            return result;
+        }
+    }
+
+    result
+}
+
+fn eval_statement(statement: &StatementVariant, env: &mut Environment) -> Object {
+    match statement {
+        StatementVariant::Expression(s) => eval_expression(&s.expression, env),
+        StatementVariant::Return(s) => {
+            let value = eval_expression(&s.return_value, env);
+            if value.is_error() {
+                return value;
+            }
+
+            Object::ReturnValue(Box::new(value))
+        }
+        StatementVariant::Let(s) => {
+            let value = eval_expression(&s.value, env);
+            if value.is_error() {
+                return value;
+            }
+
+            env.set(&s.name.value, value);
+
+            Object::Null
+        }
+    }
+}
+
+fn eval_expression(expression: &ExpressionVariant, env: &mut Environment) -> Object {
+    match expression {
+        Some(exp) => eval_expression_variant(exp, env),
+        None => Object::Null,
+    }
+}
+
+fn eval_expression_variant(expression: &ExpressionVariants, env: &mut Environment) -> Object {
+    let result = eval_expression_node(expression, env);
+
+    // Errors are tied to the innermost node they came out of. Outer
+    // nodes see the span already set and pass the error on untouched.
+    match result {
+        Object::Error(mut error) if error.span.is_none() => {
+            error.span = Some(expression.span());
+            Object::Error(error)
+        }
+        result => result,
+    }
+}
+
+fn eval_expression_node(expression: &ExpressionVariants, env: &mut Environment) -> Object {
+    match expression {
+        ExpressionVariants::Integer(int_lit) => Object::Integer(int_lit.value),
+        #[cfg(feature = "bigint")]
+        ExpressionVariants::BigInteger(int_lit) => Object::BigInteger(int_lit.value.clone()),
+        ExpressionVariants::Float(float_lit) => Object::Float(float_lit.value),
+        ExpressionVariants::Boolean(boolean) => Object::Boolean(boolean.value),
+        ExpressionVariants::String(str_lit) => Object::String(str_lit.value.clone()),
+        ExpressionVariants::Ident(ident) => match env.get(&ident.value) {
+            Some(value) => value,
+            None => match builtins::lookup(&ident.value) {
+                Some(builtin) => builtin,
+                None => Object::error(format!("identifier not found: {}", ident.value)),
+            },
+        },
+        ExpressionVariants::Prefix(pe) => {
+            let right = eval_expression_variant(&pe.right, env);
+            if right.is_error() {
+                return right;
+            }
+
+            eval_prefix_expression(&pe.operator, right)
+        }
+        ExpressionVariants::Infix(ie) => {
+            let left = eval_expression_variant(&ie.left, env);
+            if left.is_error() {
+                return left;
+            }
+
+            // Logical operators only evaluate the right operand when the
+            // left one does not already decide the result.
+            if ie.operator == "&&" || ie.operator == "||" {
+                if left.is_truthy() == (ie.operator == "||") {
+                    return Object::Boolean(left.is_truthy());
+                }
+
+                let right = eval_expression_variant(&ie.right, env);
+                if right.is_error() {
+                    return right;
+                }
+
+                return Object::Boolean(right.is_truthy());
+            }
+
+            let right = eval_expression_variant(&ie.right, env);
+            if right.is_error() {
+                return right;
+            }
+
+            eval_infix_expression(&ie.operator, left, right)
+        }
+        ExpressionVariants::If(ie) => eval_if_expression(ie, env),
+        ExpressionVariants::Function(fl) => Object::Function(Rc::new(Function {
+            parameters: fl.parameters.clone(),
+            body: fl.body.clone(),
+            env: env.clone(),
+        })),
+        ExpressionVariants::Call(ce) => {
+            let function = eval_expression_variant(&ce.function, env);
+            if function.is_error() {
+                return function;
+            }
+
+            match eval_expressions(&ce.arguments, env) {
+                Ok(arguments) => apply_function(function, &arguments),
+                Err(error) => error,
+            }
+        }
+        ExpressionVariants::Array(al) => match eval_expressions(&al.elements, env) {
+            Ok(elements) => Object::Array(elements),
+            Err(error) => error,
+        },
+        ExpressionVariants::Hash(hl) => eval_hash_literal(hl, env),
+        ExpressionVariants::Index(ie) => {
+            let left = eval_expression_variant(&ie.left, env);
+            if left.is_error() {
+                return left;
+            }
+
+            let index = eval_expression_variant(&ie.index, env);
+            if index.is_error() {
+                return index;
+            }
+
+            eval_index_expression(left, index)
+        }
+    }
+}
+
+// Evaluates expressions left to right, stopping at the first error.
+fn eval_expressions(
+    expressions: &[ExpressionVariants],
+    env: &mut Environment,
+) -> Result<Vec<Object>, Object> {
+    let mut result = Vec::with_capacity(expressions.len());
+
+    for expression in expressions.iter() {
+        let value = eval_expression_variant(expression, env);
+        if value.is_error() {
+            return Err(value);
+        }
+
+        result.push(value);
+    }
+
+    Ok(result)
+}
+
+fn eval_hash_literal(hash: &HashLiteral, env: &mut Environment) -> Object {
+    let mut pairs = BTreeMap::new();
+
+    for (key_expression, value_expression) in hash.pairs.iter() {
+        let key = eval_expression_variant(key_expression, env);
+        if key.is_error() {
+            return key;
+        }
+
+        let hash_key = match key.hash_key() {
+            Some(hash_key) => hash_key,
+            None => return unusable_as_hash_key(&key),
+        };
+
+        let value = eval_expression_variant(value_expression, env);
+        if value.is_error() {
+            return value;
+        }
+
+        pairs.insert(hash_key, value);
+    }
+
+    Object::Hash(pairs)
+}
+
+fn eval_index_expression(left: Object, index: Object) -> Object {
+    match (&left, &index) {
+        (Object::Hash(pairs), _) => match index.hash_key() {
+            Some(key) => pairs.get(&key).cloned().unwrap_or(Object::Null),
+            None => unusable_as_hash_key(&index),
+        },
+        (Object::Array(elements), Object::Integer(i)) => {
+            match usize::try_from(*i).ok().and_then(|i| elements.get(i)) {
+                Some(element) => element.clone(),
+                None => Object::error(format!(
+                    "index out of range: {} (length {})",
+                    i,
+                    elements.len()
+                )),
+            }
+        }
+        // Big integers are past i64, so never a valid index.
+        #[cfg(feature = "bigint")]
+        (Object::Array(elements), Object::BigInteger(i)) => Object::error(format!(
+            "index out of range: {} (length {})",
+            i,
+            elements.len()
+        )),
+        _ => Object::error(format!(
+            "index operator not supported: {}[{}]",
+            left.type_name(),
+            index.type_name()
+        )),
+    }
+}
+
+fn apply_function(function: Object, arguments: &[Object]) -> Object {
+    match function {
+        Object::Function(function) => {
+            if function.parameters.len() != arguments.len() {
+                return Object::error(format!(
+                    "wrong number of arguments: want={}, got={}",
+                    function.parameters.len(),
+                    arguments.len()
+                ));
+            }
+
+            // The body runs in a fresh scope enclosed by the one the
+            // function was defined in, not the one it is called from.
+            let mut env = Environment::new_enclosed(&function.env);
+            for (parameter, argument) in function.parameters.iter().zip(arguments) {
+                env.set(&parameter.value, argument.clone());
+            }
+
+            let depth = CALL_DEPTH.get();
+            if depth >= MAX_CALL_DEPTH {
+                return Object::error(format!("maximum call depth exceeded: {}", MAX_CALL_DEPTH));
+            }
+
+            CALL_DEPTH.set(depth + 1);
+            let result = eval_block_statement(&function.body, &mut env);
+            CALL_DEPTH.set(depth);
+
+            match result {
+                Object::ReturnValue(value) => *value,
+                result => result,
+            }
+        }
+        Object::Builtin(builtin) => {
+            if let Arity::Exact(want) = builtin.arity {
+                if want != arguments.len() {
+                    return Object::error(format!(
+                        "wrong number of arguments: want={}, got={}",
+                        want,
+                        arguments.len()
+                    ));
+                }
+            }
+
+            match (builtin.function)(arguments) {
+                Ok(result) => result,
+                Err(message) => Object::error(message),
+            }
+        }
+        _ => Object::error(format!("not a function: {}", function.type_name())),
+    }
+}
+
+fn eval_prefix_expression(operator: &str, right: Object) -> Object {
+    match operator {
+        "!" => Object::Boolean(!right.is_truthy()),
+        "-" => match right {
+            Object::Integer(value) => match value.checked_neg() {
+                Some(value) => Object::Integer(value),
+                #[cfg(feature = "bigint")]
+                None => Object::big_integer(-BigInt::from(value)),
+                #[cfg(not(feature = "bigint"))]
+                None => Object::error(format!("integer overflow: -({})", value)),
+            },
+            #[cfg(feature = "bigint")]
+            Object::BigInteger(value) => Object::big_integer(-value),
+            Object::Float(value) => Object::Float(-value),
+            _ => Object::error(format!("unknown operator: -{}", right.type_name())),
+        },
+        _ => Object::error(format!(
+            "unknown operator: {}{}",
+            operator,
+            right.type_name()
+        )),
+    }
+}
+
+fn eval_infix_expression(operator: &str, left: Object, right: Object) -> Object {
+    match (&left, &right) {
+        (Object::Integer(l), Object::Integer(r)) => eval_integer_infix_expression(operator, *l, *r),
+        // Mixing an integer with a float turns the integer into a float.
+        (Object::Float(l), Object::Float(r)) => eval_float_infix_expression(operator, *l, *r),
+        (Object::Integer(l), Object::Float(r)) => {
+            eval_float_infix_expression(operator, *l as f64, *r)
+        }
+        (Object::Float(l), Object::Integer(r)) => {
+            eval_float_infix_expression(operator, *l, *r as f64)
+        }
+        #[cfg(feature = "bigint")]
+        (Object::BigInteger(_), Object::Integer(_) | Object::BigInteger(_))
+        | (Object::Integer(_), Object::BigInteger(_)) => {
+            match (left.to_big_integer(), right.to_big_integer()) {
+                (Some(l), Some(r)) => eval_big_integer_infix_expression(operator, l, r),
+                _ => unknown_infix_operator(operator, &left, &right),
+            }
+        }
+        #[cfg(feature = "bigint")]
+        (Object::BigInteger(l), Object::Float(r)) => {
+            eval_float_infix_expression(operator, l.to_f64().unwrap_or(f64::NAN), *r)
+        }
+        #[cfg(feature = "bigint")]
+        (Object::Float(l), Object::BigInteger(r)) => {
+            eval_float_infix_expression(operator, *l, r.to_f64().unwrap_or(f64::NAN))
+        }
+        (Object::String(l), Object::String(r)) => match operator {
+            "+" => Object::String(format!("{}{}", l, r)),
+            "==" => Object::Boolean(l == r),
+            "!=" => Object::Boolean(l != r),
+            _ => unknown_infix_operator(operator, &left, &right),
+        },
+        (Object::Boolean(l), Object::Boolean(r)) => match operator {
+            "==" => Object::Boolean(l == r),
+            "!=" => Object::Boolean(l != r),
+            _ => unknown_infix_operator(operator, &left, &right),
+        },
+        _ if left.type_name() != right.type_name() => Object::error(format!(
+            "type mismatch: {} {} {}",
+            left.type_name(),
+            operator,
+            right.type_name()
+        )),
+        _ => unknown_infix_operator(operator, &left, &right),
+    }
+}
+
+fn eval_integer_infix_expression(operator: &str, left: i64, right: i64) -> Object {
+    match operator {
+        "+" => checked_integer(left.checked_add(right), operator, left, right),
+        "-" => checked_integer(left.checked_sub(right), operator, left, right),
+        "*" => checked_integer(left.checked_mul(right), operator, left, right),
+        "/" | "%" if right == 0 => {
+            Object::error(format!("division by zero: {} {} {}", left, operator, right))
+        }
+        "/" => checked_integer(left.checked_div(right), operator, left, right),
+        "%" => checked_integer(left.checked_rem(right), operator, left, right),
+        "**" if right < 0 => Object::error(format!("negative exponent: {} ** {}", left, right)),
+        // 0, 1 and -1 only depend on whether the exponent is 0, odd or
+        // even, so an exponent past u32 still has an answer.
+        "**" if (-1..=1).contains(&left) => {
+            let exponent = if right == 0 {
+                0
+            } else {
+                2 - (right % 2) as u32
+            };
+            Object::Integer(left.pow(exponent))
+        }
+        "**" => checked_integer(
+            u32::try_from(right)
+                .ok()
+                .and_then(|exponent| left.checked_pow(exponent)),
+            operator,
+            left,
+            right,
+        ),
+        "<" => Object::Boolean(left < right),
+        ">" => Object::Boolean(left > right),
+        "<=" => Object::Boolean(left <= right),
+        ">=" => Object::Boolean(left >= right),
+        "==" => Object::Boolean(left == right),
+        "!=" => Object::Boolean(left != right),
+        _ => Object::error(format!("unknown operator: INTEGER {} INTEGER", operator)),
+    }
+}
+
+// Integer arithmetic never wraps. A result that does not fit in an i64
+// becomes a Mono error, or a big integer with the `bigint` feature.
+fn checked_integer(result: Option<i64>, operator: &str, left: i64, right: i64) -> Object {
+    match result {
+        Some(value) => Object::Integer(value),
+        #[cfg(feature = "bigint")]
+        None => eval_big_integer_infix_expression(operator, left.into(), right.into()),
+        #[cfg(not(feature = "bigint"))]
+        None => Object::error(format!("integer overflow: {} {} {}", left, operator, right)),
+    }
+}
+
+// About a million bits, or over 300,000 decimal digits.
+#[cfg(feature = "bigint")]
+const MAX_BIG_INTEGER_BITS: u64 = 1 << 20;
+
+#[cfg(feature = "bigint")]
+fn eval_big_integer_infix_expression(operator: &str, left: BigInt, right: BigInt) -> Object {
+    match operator {
+        "+" => Object::big_integer(left + right),
+        "-" => Object::big_integer(left - right),
+        "*" => Object::big_integer(left * right),
+        "/" | "%" if right.is_zero() => {
+            Object::error(format!("division by zero: {} {} {}", left, operator, right))
+        }
+        "/" => Object::big_integer(left / right),
+        "%" => Object::big_integer(left % right),
+        "**" if right.is_negative() => {
+            Object::error(format!("negative exponent: {} ** {}", left, right))
+        }
+        // Only 0, 1 and -1 stay small whatever the power, and they only
+        // depend on whether the exponent is 0, odd or even.
+        "**" if left.bits() <= 1 => {
+            let exponent = if right.is_zero() {
+                0
+            } else {
+                2 - u32::from(right.bit(0))
+            };
+            Object::big_integer(left.pow(exponent))
+        }
+        // A result of `bits * exponent` bits would take too long to build
+        // past the limit.
+        "**" => match right.to_u32() {
+            Some(exponent)
+                if left.bits().saturating_mul(exponent.into()) <= MAX_BIG_INTEGER_BITS =>
+            {
+                Object::big_integer(left.pow(exponent))
+            }
+            _ => Object::error(format!("exponent too large: {} ** {}", left, right)),
+        },
+        "<" => Object::Boolean(left < right),
+        ">" => Object::Boolean(left > right),
+        "<=" => Object::Boolean(left <= right),
+        ">=" => Object::Boolean(left >= right),
+        "==" => Object::Boolean(left == right),
+        "!=" => Object::Boolean(left != right),
+        _ => Object::error(format!("unknown operator: INTEGER {} INTEGER", operator)),
+    }
+}
+
+fn eval_float_infix_expression(operator: &str, left: f64, right: f64) -> Object {
+    match operator {
+        "+" => Object::Float(left + right),
+        "-" => Object::Float(left - right),
+        "*" => Object::Float(left * right),
+        "/" => Object::Float(left / right),
+        "%" => Object::Float(left % right),
+        "**" => Object::Float(left.powf(right)),
+        "<" => Object::Boolean(left < right),
+        ">" => Object::Boolean(left > right),
+        "<=" => Object::Boolean(left <= right),
+        ">=" => Object::Boolean(left >= right),
+        "==" => Object::Boolean(left == right),
+        "!=" => Object::Boolean(left != right),
+        _ => Object::error(format!("unknown operator: FLOAT {} FLOAT", operator)),
+    }
+}
+
+fn eval_if_expression(expression: &IfExpression, env: &mut Environment) -> Object {
+    let condition = eval_expression_variant(&expression.condition, env);
+    if condition.is_error() {
+        return condition;
+    }
+
+    if condition.is_truthy() {
+        eval_block_statement(&expression.consequence, env)
+    } else if let Some(alternative) = &expression.alternative {
+        eval_block_statement(alternative, env)
+    } else {
+        Object::Null
+    }
+}
+
+fn unusable_as_hash_key(key: &Object) -> Object {
+    Object::error(format!("unusable as hash key: {}", key.type_name()))
+}
+
+fn unknown_infix_operator(operator: &str, left: &Object, right: &Object) -> Object {
+    Object::error(format!(
+        "unknown operator: {} {} {}",
+        left.type_name(),
+        operator,
+        right.type_name()
+    ))
+}
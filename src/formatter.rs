@@ -0,0 +1,413 @@
+use crate::{
+    ast::{
+        quote_string, BlockStatement, ExpressionVariants, IfExpression, Node, Program,
+        StatementVariant,
+    },
+    diagnostic::Diagnostic,
+    lexer::Lexer,
+    parser::{precedences, Parser, Precedence},
+    token::{Comment, TokenType},
+};
+
+const INDENT: &str = "    ";
+
+// Blocks holding a single short expression stay on one line.
+const MAX_INLINE_BLOCK: usize = 40;
+
+// Formats Mono source canonically: four space indentation, one statement
+// per line, only the parentheses the grammar needs, and at most one blank
+// line between statements. Comments are kept, though a comment written
+// inside an expression moves to the end of its statement.
+//
+// Source that does not parse is left alone and its diagnostics returned.
+pub fn format(source: &str) -> Result<String, Vec<Diagnostic>> {
+    let mut parser = Parser::new(Lexer::new(source));
+    let program = parser.parse_program();
+
+    if !parser.errors().is_empty() {
+        return Err(parser.errors().to_vec());
+    }
+
+    // The parser does not keep comments, so they are read separately.
+    let mut lexer = Lexer::new(source);
+    while lexer.next_token().typ != TokenType::EOF {}
+
+    let mut formatter = Formatter::new(source, lexer.comments().to_vec());
+
+    Ok(formatter.program(&program))
+}
+
+struct Formatter {
+    comments: Vec<Comment>,
+    // Index of the first comment not written out yet.
+    next_comment: usize,
+    // Comments passed over inside the statement being formatted, such as
+    // one in an `if` condition, which are written after it.
+    pending: Vec<Comment>,
+    // Byte offsets where each line of the source starts.
+    line_starts: Vec<usize>,
+    // Source line of the last statement or comment written out.
+    last_line: usize,
+}
+
+impl Formatter {
+    fn new(source: &str, comments: Vec<Comment>) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(source.match_indices('\n').map(|(i, _)| i + 1));
+
+        Self {
+            comments,
+            next_comment: 0,
+            pending: Vec::new(),
+            line_starts,
+            last_line: 0,
+        }
+    }
+
+    fn program(&mut self, program: &Program) -> String {
+        let mut out = String::new();
+
+        self.statements(&mut out, &program.statements, 0, usize::MAX, false);
+
+        out
+    }
+
+    // Writes one statement per line at `indent`, along with the comments
+    // found before `end`.
+    fn statements(
+        &mut self,
+        out: &mut String,
+        statements: &[StatementVariant],
+        indent: usize,
+        end: usize,
+        in_block: bool,
+    ) {
+        // Where the `;` goes if the `if` just written needs one.
+        let mut open_if: Option<usize> = None;
+
+        for (i, statement) in statements.iter().enumerate() {
+            let span = statement.span();
+
+            self.leading_comments(out, span.start, indent);
+            self.blank_line(out, self.line_of(span.start));
+
+            let last = in_block && i == statements.len() - 1;
+            let outer_pending = std::mem::take(&mut self.pending);
+            let text = self.statement(statement, indent, last);
+
+            // Without a `;` the statement after an `if` would be read as
+            // an operator or call applied to it, as in `if (a) { b } -c`.
+            if let Some(offset) = open_if.take() {
+                if text.starts_with(['(', '[', '-', '!', '{']) {
+                    out.insert(offset, ';');
+                }
+            }
+
+            out.push_str(&INDENT.repeat(indent));
+            out.push_str(&text);
+            if is_if_statement(statement) {
+                open_if = Some(out.len());
+            }
+            let last_line = self.line_of(span.end.saturating_sub(1));
+            self.last_line = last_line;
+            self.trailing_comments(out, span.end, end, indent);
+            self.last_line = self.last_line.max(last_line);
+            self.pending = outer_pending;
+            out.push('\n');
+        }
+
+        self.leading_comments(out, end, indent);
+    }
+
+    fn statement(&mut self, statement: &StatementVariant, indent: usize, last: bool) -> String {
+        match statement {
+            StatementVariant::Let(ls) => match &ls.value {
+                Some(value) => format!(
+                    "let {} = {};",
+                    ls.name.value,
+                    self.expression(value, indent)
+                ),
+                None => format!("let {};", ls.name.value),
+            },
+            StatementVariant::Return(rs) => match &rs.return_value {
+                Some(value) => format!("return {};", self.expression(value, indent)),
+                None => "return;".to_string(),
+            },
+            StatementVariant::Expression(es) => match &es.expression {
+                // An `if` ends in a block and the last expression of a
+                // block is its value, so neither takes a semicolon. An
+                // `if` gets one back in `statements` when it needs it.
+                Some(expression @ ExpressionVariants::If(_)) => self.expression(expression, indent),
+                Some(expression) if last => self.expression(expression, indent),
+                Some(expression) => format!("{};", self.expression(expression, indent)),
+                None => String::new(),
+            },
+        }
+    }
+
+    fn block(&mut self, block: &BlockStatement, indent: usize) -> String {
+        let end = block.span.end;
+
+        // Comments before the `{` are in the header, not the body.
+        while self.has_comments_before(block.span.start) {
+            self.pending.push(self.comments[self.next_comment].clone());
+            self.next_comment += 1;
+        }
+
+        if !self.has_comments_before(end) {
+            if block.statements.is_empty() {
+                return "{}".to_string();
+            }
+
+            if let [StatementVariant::Expression(es)] = block.statements.as_slice() {
+                if let Some(expression) = &es.expression {
+                    let text = self.expression(expression, indent + 1);
+
+                    if !text.contains('\n') && text.len() <= MAX_INLINE_BLOCK {
+                        return format!("{{ {} }}", text);
+                    }
+                }
+            }
+        }
+
+        let mut out = String::from("{\n");
+        self.last_line = self.line_of(block.span.start);
+        self.statements(&mut out, &block.statements, indent + 1, end, true);
+        out.push_str(&INDENT.repeat(indent));
+        out.push('}');
+
+        out
+    }
+
+    fn expression(&mut self, expression: &ExpressionVariants, indent: usize) -> String {
+        match expression {
+            ExpressionVariants::Ident(ident) => ident.value.clone(),
+            // Numbers keep the way they were written, such as `0xFF`.
+            ExpressionVariants::Integer(int_lit) => int_lit.token.literal.clone(),
+            #[cfg(feature = "bigint")]
+            ExpressionVariants::BigInteger(int_lit) => int_lit.token.literal.clone(),
+            ExpressionVariants::Float(float_lit) => float_lit.token.literal.clone(),
+            ExpressionVariants::String(str_lit) => quote_string(&str_lit.value),
+            ExpressionVariants::Boolean(b) => b.value.to_string(),
+            ExpressionVariants::Prefix(pe) => {
+                let right = self.operand(&pe.right, Precedence::PREFIX.index(), indent);
+
+                format!("{}{}", pe.operator, right)
+            }
+            ExpressionVariants::Infix(ie) => {
+                let precedence = precedence(expression);
+                let right_associative = ie.token.typ == TokenType::POWER;
+
+                // Operands binding as tightly as the operator itself only
+                // need parentheses on the side it does not associate to.
+                let (left, right) = if right_associative {
+                    (precedence + 1, precedence)
+                } else {
+                    (precedence, precedence + 1)
+                };
+
+                format!(
+                    "{} {} {}",
+                    self.operand(&ie.left, left, indent),
+                    ie.operator,
+                    self.operand(&ie.right, right, indent)
+                )
+            }
+            ExpressionVariants::If(ie) => self.if_expression(ie, indent),
+            ExpressionVariants::Function(fl) => {
+                let parameters: Vec<&str> =
+                    fl.parameters.iter().map(|p| p.value.as_str()).collect();
+
+                format!(
+                    "fn({}) {}",
+                    parameters.join(", "),
+                    self.block(&fl.body, indent)
+                )
+            }
+            ExpressionVariants::Call(ce) => {
+                let function = self.operand(&ce.function, Precedence::CALL.index(), indent);
+                let arguments = self.list(&ce.arguments, indent);
+
+                format!("{}({})", function, arguments)
+            }
+            ExpressionVariants::Array(al) => format!("[{}]", self.list(&al.elements, indent)),
+            ExpressionVariants::Index(ie) => {
+                let left = self.operand(&ie.left, Precedence::CALL.index(), indent);
+                let index = self.expression(&ie.index, indent);
+
+                format!("{}[{}]", left, index)
+            }
+            ExpressionVariants::Hash(hl) => {
+                let pairs: Vec<String> = hl
+                    .pairs
+                    .iter()
+                    .map(|(key, value)| {
+                        format!(
+                            "{}: {}",
+                            self.expression(key, indent),
+                            self.expression(value, indent)
+                        )
+                    })
+                    .collect();
+
+                format!("{{{}}}", pairs.join(", "))
+            }
+        }
+    }
+
+    fn if_expression(&mut self, expression: &IfExpression, indent: usize) -> String {
+        let mut out = format!(
+            "if ({}) {}",
+            self.expression(&expression.condition, indent),
+            self.block(&expression.consequence, indent)
+        );
+
+        if let Some(alternative) = &expression.alternative {
+            // The parser stores `else if` as a block holding only the
+            // nested if, which is printed back the same way.
+            if let [StatementVariant::Expression(es)] = alternative.statements.as_slice() {
+                if let Some(ExpressionVariants::If(nested)) = &es.expression {
+                    out.push_str(" else ");
+                    out.push_str(&self.if_expression(nested, indent));
+
+                    return out;
+                }
+            }
+
+            out.push_str(" else ");
+            out.push_str(&self.block(alternative, indent));
+        }
+
+        out
+    }
+
+    // Formats a subexpression, wrapping it in parentheses when it binds
+    // less tightly than `minimum`.
+    fn operand(
+        &mut self,
+        expression: &ExpressionVariants,
+        minimum: usize,
+        indent: usize,
+    ) -> String {
+        let text = self.expression(expression, indent);
+
+        if precedence(expression) < minimum {
+            format!("({})", text)
+        } else {
+            text
+        }
+    }
+
+    fn list(&mut self, expressions: &[ExpressionVariants], indent: usize) -> String {
+        let items: Vec<String> = expressions
+            .iter()
+            .map(|e| self.expression(e, indent))
+            .collect();
+
+        items.join(", ")
+    }
+
+    fn has_comments_before(&self, offset: usize) -> bool {
+        self.comments
+            .get(self.next_comment)
+            .is_some_and(|comment| comment.span.start < offset)
+    }
+
+    // Writes the comments that come before `offset`, each on its own line.
+    fn leading_comments(&mut self, out: &mut String, offset: usize, indent: usize) {
+        while self.has_comments_before(offset) {
+            let comment = self.comments[self.next_comment].clone();
+            self.next_comment += 1;
+
+            self.blank_line(out, comment.span.line);
+            out.push_str(&INDENT.repeat(indent));
+            out.push_str(&comment.text);
+            out.push('\n');
+            self.last_line = self.line_of(comment.span.end.saturating_sub(1));
+        }
+    }
+
+    // Writes the comments left inside a statement ending at `end`, or
+    // starting on the line it ends on, after it. Comments past `limit`
+    // belong to an enclosing statement.
+    fn trailing_comments(&mut self, out: &mut String, end: usize, limit: usize, indent: usize) {
+        let mut after_line_comment = false;
+
+        for comment in std::mem::take(&mut self.pending) {
+            after_line_comment = self.trailing_comment(out, &comment, after_line_comment, indent);
+        }
+
+        while let Some(comment) = self.comments.get(self.next_comment).cloned() {
+            let inside = comment.span.start < end;
+            let same_line = comment.span.line == self.last_line && comment.span.start < limit;
+
+            if !inside && !same_line {
+                break;
+            }
+
+            self.next_comment += 1;
+            after_line_comment = self.trailing_comment(out, &comment, after_line_comment, indent);
+        }
+    }
+
+    // Writes one comment after a statement, returning whether it was a
+    // `//` comment.
+    fn trailing_comment(
+        &mut self,
+        out: &mut String,
+        comment: &Comment,
+        after_line_comment: bool,
+        indent: usize,
+    ) -> bool {
+        // Nothing can follow a `//` comment on the same line.
+        if after_line_comment {
+            out.push('\n');
+            out.push_str(&INDENT.repeat(indent));
+        } else {
+            out.push(' ');
+        }
+
+        out.push_str(&comment.text);
+        self.last_line = self.line_of(comment.span.end.saturating_sub(1));
+
+        comment.text.starts_with("//")
+    }
+
+    // Keeps a single blank line where the source had one or more.
+    fn blank_line(&self, out: &mut String, line: usize) {
+        let at_start = out.is_empty() || out.ends_with("{\n");
+
+        if !at_start && line > self.last_line + 1 {
+            out.push('\n');
+        }
+    }
+
+    fn line_of(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|start| *start <= offset)
+    }
+}
+
+fn is_if_statement(statement: &StatementVariant) -> bool {
+    matches!(
+        statement,
+        StatementVariant::Expression(es) if matches!(es.expression, Some(ExpressionVariants::If(_)))
+    )
+}
+
+// How tightly an expression binds. Literals and other expressions that
+// never need parentheses bind the tightest.
+fn precedence(expression: &ExpressionVariants) -> usize {
+    match expression {
+        ExpressionVariants::Infix(ie) => precedences()
+            .get(&ie.token.typ)
+            .map_or(Precedence::LOWEST.index(), |p| p.index()),
+        ExpressionVariants::Prefix(_) => Precedence::PREFIX.index(),
+        ExpressionVariants::Call(_) => Precedence::CALL.index(),
+        ExpressionVariants::Index(_) => Precedence::INDEX.index(),
+        // `-9223372036854775808` is read as one literal but binds like `-`.
+        ExpressionVariants::Integer(il) if il.token.literal.starts_with('-') => {
+            Precedence::PREFIX.index()
+        }
+        _ => usize::MAX,
+    }
+}
@@ -1,10 +1,18 @@
-use crate::token::{Token, TokenType};
+use crate::token::{Comment, Span, Token, TokenType};
 
 pub struct Lexer {
     input: String,
     position: usize,
     read_position: usize,
     pub ch: char,
+    // Location of `ch`, both starting at 1.
+    line: usize,
+    column: usize,
+    // `///` comments read since the last token was returned.
+    doc_comments: Vec<String>,
+    comments: Vec<Comment>,
+    // Added to the byte offsets in every span.
+    offset: usize,
 }
 
 impl Lexer {
@@ -13,18 +21,47 @@ impl Lexer {
             input: input.to_string(),
             position: 0,
             read_position: 0,
-            ch: ' ' as char,
+            ch: ' ',
+            line: 1,
+            column: 1,
+            doc_comments: Vec::new(),
+            comments: Vec::new(),
+            offset: 0,
         };
         lexer.read_char();
 
         lexer
     }
 
-    pub fn next_token(&mut self) -> Token {
-        let mut token: Token = Token::new(TokenType::ILLEGAL, ' ');
+    // A lexer whose spans start at byte `offset` instead of 0, for input
+    // that follows earlier input, as each entry in the REPL does. Lines
+    // and columns still count from the start of `input`.
+    pub fn with_offset(input: &str, offset: usize) -> Self {
+        Self {
+            offset,
+            ..Self::new(input)
+        }
+    }
 
+    pub fn next_token(&mut self) -> Token {
         self.skip_whitespace();
 
+        let (start, line, column) = (self.position, self.line, self.column);
+
+        let mut token = self.read_token();
+        token.span = Span {
+            start: start + self.offset,
+            end: self.position + self.offset,
+            line,
+            column,
+        };
+
+        token
+    }
+
+    fn read_token(&mut self) -> Token {
+        let mut token: Token = Token::new(TokenType::ILLEGAL, ' ');
+
         match self.ch {
             '=' => {
                 if self.peek_char() == '=' {
@@ -36,6 +73,7 @@ impl Lexer {
                 }
             }
             ';' => token = Token::new(TokenType::SEMICOLON, self.ch),
+            ':' => token = Token::new(TokenType::COLON, self.ch),
             '(' => token = Token::new(TokenType::LPAREN, self.ch),
             ')' => token = Token::new(TokenType::RPAREN, self.ch),
             ',' => token = Token::new(TokenType::COMMA, self.ch),
@@ -50,12 +88,77 @@ impl Lexer {
                     token = Token::new(TokenType::BANG, self.ch)
                 }
             }
-            '/' => token = Token::new(TokenType::SLASH, self.ch),
-            '*' => token = Token::new(TokenType::ASTERISK, self.ch),
-            '<' => token = Token::new(TokenType::LT, self.ch),
-            '>' => token = Token::new(TokenType::GT, self.ch),
+            '/' => {
+                // Closed comments are skipped as whitespace, so this
+                // one runs to the end of the input.
+                if self.peek_char() == '*' {
+                    while self.ch != '\0' {
+                        self.read_char();
+                    }
+                    token.literal = String::from("/*");
+                    return token;
+                }
+
+                token = Token::new(TokenType::SLASH, self.ch)
+            }
+            '*' => {
+                if self.peek_char() == '*' {
+                    self.read_char();
+                    token.typ = TokenType::POWER;
+                    token.literal = String::from("**");
+                } else {
+                    token = Token::new(TokenType::ASTERISK, self.ch)
+                }
+            }
+            '%' => token = Token::new(TokenType::PERCENT, self.ch),
+            '<' => {
+                if self.peek_char() == '=' {
+                    self.read_char();
+                    token.typ = TokenType::LTE;
+                    token.literal = String::from("<=");
+                } else {
+                    token = Token::new(TokenType::LT, self.ch)
+                }
+            }
+            '>' => {
+                if self.peek_char() == '=' {
+                    self.read_char();
+                    token.typ = TokenType::GTE;
+                    token.literal = String::from(">=");
+                } else {
+                    token = Token::new(TokenType::GT, self.ch)
+                }
+            }
+            // A single `&` or `|` is not an operator and stays ILLEGAL.
+            '&' => {
+                if self.peek_char() == '&' {
+                    self.read_char();
+                    token.typ = TokenType::AND;
+                    token.literal = String::from("&&");
+                } else {
+                    token.literal = self.ch.to_string();
+                }
+            }
+            '|' => {
+                if self.peek_char() == '|' {
+                    self.read_char();
+                    token.typ = TokenType::OR;
+                    token.literal = String::from("||");
+                } else {
+                    token.literal = self.ch.to_string();
+                }
+            }
             '{' => token = Token::new(TokenType::LBRACE, self.ch),
             '}' => token = Token::new(TokenType::RBRACE, self.ch),
+            '[' => token = Token::new(TokenType::LBRACKET, self.ch),
+            ']' => token = Token::new(TokenType::RBRACKET, self.ch),
+            '"' => match self.read_string() {
+                Ok(value) => {
+                    token.typ = TokenType::STRING;
+                    token.literal = value;
+                }
+                Err(raw) => token.literal = raw,
+            },
             '\0' => token = Token::new(TokenType::EOF, ' '),
             _ => {
                 if is_letter(self.ch) {
@@ -63,9 +166,10 @@ impl Lexer {
                     token.typ = TokenType::serialize(&token.literal);
                     return token;
                 } else if is_digit(self.ch) {
-                    token.literal = self.read_number();
-                    token.typ = TokenType::INT;
+                    (token.literal, token.typ) = self.read_number();
                     return token;
+                } else {
+                    token.literal = self.ch.to_string();
                 }
             }
         }
@@ -75,14 +179,125 @@ impl Lexer {
         token
     }
 
-    fn read_number(&mut self) -> String {
+    // Reads an integer, or a float when the digits are followed by a
+    // fraction (`3.14`) or an exponent (`1e-9`). A `.` or `e` that is not
+    // followed by digits is left for the next token.
+    fn read_number(&mut self) -> (String, TokenType) {
         let position = self.position;
+        let mut typ = TokenType::INT;
 
-        while is_digit(self.ch) {
+        if self.ch == '0' && matches!(self.peek_char(), 'x' | 'X' | 'o' | 'O' | 'b' | 'B') {
+            self.read_char();
             self.read_char();
+
+            // Every letter and digit is taken, so a bad digit such as
+            // the 2 in `0b102` is reported by the parser.
+            while self.ch.is_ascii_alphanumeric() || self.ch == '_' {
+                self.read_char();
+            }
+
+            return (self.input[position..self.position].to_string(), typ);
         }
 
-        self.input[position..self.position].to_string()
+        self.read_digits();
+
+        if self.ch == '.' && is_digit(self.peek_char()) {
+            typ = TokenType::FLOAT;
+            self.read_char();
+            self.read_digits();
+        }
+
+        if self.ch == 'e' || self.ch == 'E' {
+            let sign = matches!(self.peek_char(), '+' | '-');
+            let digit = if sign { 1 } else { 0 };
+
+            if is_digit(self.peek_nth_char(digit)) {
+                typ = TokenType::FLOAT;
+                self.read_char();
+                if sign {
+                    self.read_char();
+                }
+                self.read_digits();
+            }
+        }
+
+        (self.input[position..self.position].to_string(), typ)
+    }
+
+    // Digits may be grouped with `_`, as in `1_000_000`.
+    fn read_digits(&mut self) {
+        while is_digit(self.ch) || self.ch == '_' {
+            self.read_char();
+        }
+    }
+
+    // Reads a string literal up to and including its closing quote,
+    // decoding escape sequences along the way. On an unterminated string
+    // or a bad escape the raw source text is returned as the error.
+    fn read_string(&mut self) -> Result<String, String> {
+        let position = self.position;
+        let mut value = String::new();
+        let mut valid = true;
+
+        loop {
+            self.read_char();
+
+            match self.ch {
+                '"' => break,
+                '\0' => {
+                    return Err(self.input[position..self.position].to_string());
+                }
+                '\\' => {
+                    self.read_char();
+
+                    match self.ch {
+                        'n' => value.push('\n'),
+                        't' => value.push('\t'),
+                        'r' => value.push('\r'),
+                        '"' => value.push('"'),
+                        '\\' => value.push('\\'),
+                        'u' => match self.read_unicode_escape() {
+                            Some(ch) => value.push(ch),
+                            None => valid = false,
+                        },
+                        '\0' => {
+                            return Err(self.input[position..self.position].to_string());
+                        }
+                        _ => valid = false,
+                    }
+                }
+                ch => value.push(ch),
+            }
+        }
+
+        if valid {
+            Ok(value)
+        } else {
+            Err(self.input[position..=self.position].to_string())
+        }
+    }
+
+    // Reads the `{XXXX}` part of a `\u{XXXX}` escape.
+    fn read_unicode_escape(&mut self) -> Option<char> {
+        if self.peek_char() != '{' {
+            return None;
+        }
+        self.read_char();
+
+        let mut digits = String::new();
+        while self.peek_char().is_ascii_hexdigit() {
+            self.read_char();
+            digits.push(self.ch);
+        }
+
+        if self.peek_char() != '}' || digits.is_empty() || digits.len() > 6 {
+            return None;
+        }
+        self.read_char();
+
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(char::from_u32)
     }
 
     fn read_identifier(&mut self) -> String {
@@ -96,27 +311,149 @@ impl Lexer {
     }
 
     pub fn read_char(&mut self) {
-        if self.read_position >= self.input.len() {
-            self.ch = '\0';
-        } else {
-            self.ch = self.input.as_bytes()[self.read_position] as char;
+        // Once we reach the end we stay there.
+        if self.read_position > self.input.len() {
+            return;
+        }
+
+        if self.ch == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else if self.read_position > 0 {
+            self.column += 1;
         }
 
         self.position = self.read_position;
 
-        self.read_position += 1;
+        // Positions are byte offsets into the input, so they always
+        // advance by the UTF-8 length of the character just read.
+        match self.input[self.read_position..].chars().next() {
+            Some(ch) => {
+                self.ch = ch;
+                self.read_position += ch.len_utf8();
+            }
+            None => {
+                self.ch = '\0';
+                self.read_position += 1;
+            }
+        }
     }
 
     fn peek_char(&self) -> char {
-        if self.read_position >= self.input.len() {
-            '0'
-        } else {
-            self.input.as_bytes()[self.read_position] as char
-        }
+        self.peek_nth_char(0)
+    }
+
+    // The character `n` places after the next one.
+    fn peek_nth_char(&self, n: usize) -> char {
+        self.input
+            .get(self.read_position..)
+            .and_then(|rest| rest.chars().nth(n))
+            .unwrap_or('\0')
     }
 
+    // Doc comments that came right before the last token returned by
+    // `next_token`, one entry per line.
+    pub fn take_doc_comments(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.doc_comments)
+    }
+
+    // Every comment skipped so far, in source order.
+    pub fn comments(&self) -> &[Comment] {
+        &self.comments
+    }
+
+    fn record_comment(&mut self, start: (usize, usize, usize)) {
+        let (position, line, column) = start;
+
+        self.comments.push(Comment {
+            text: self.input[position..self.position].trim_end().to_string(),
+            span: Span {
+                start: position + self.offset,
+                end: self.position + self.offset,
+                line,
+                column,
+            },
+        });
+    }
+
+    // Skips whitespace along with `//` and `/* */` comments.
     pub fn skip_whitespace(&mut self) {
-        while self.ch == ' ' || self.ch == '\t' || self.ch == '\n' || self.ch == '\r' {
+        loop {
+            match self.ch {
+                ' ' | '\t' | '\n' | '\r' => self.read_char(),
+                '/' if self.peek_char() == '/' => self.skip_line_comment(),
+                '/' if self.peek_char() == '*' => {
+                    if !self.skip_block_comment() {
+                        return;
+                    }
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn skip_line_comment(&mut self) {
+        let position = self.position;
+        let start = (self.position, self.line, self.column);
+
+        while self.ch != '\n' && self.ch != '\0' {
+            self.read_char();
+        }
+
+        self.record_comment(start);
+
+        let text = &self.input[position..self.position];
+        // `////` and longer are ordinary comments, as in Rust.
+        if let Some(doc) = text.strip_prefix("///") {
+            if !doc.starts_with('/') {
+                let doc = doc.strip_prefix(' ').unwrap_or(doc);
+                self.doc_comments.push(doc.trim_end().to_string());
+            }
+        }
+    }
+
+    // Skips a block comment, including any nested in it. An unterminated
+    // comment is left in place for `read_token` to report.
+    fn skip_block_comment(&mut self) -> bool {
+        let saved = (
+            self.position,
+            self.read_position,
+            self.ch,
+            self.line,
+            self.column,
+        );
+        let start = (self.position, self.line, self.column);
+        let mut depth = 0;
+
+        loop {
+            match (self.ch, self.peek_char()) {
+                ('\0', _) => {
+                    (
+                        self.position,
+                        self.read_position,
+                        self.ch,
+                        self.line,
+                        self.column,
+                    ) = saved;
+                    return false;
+                }
+                ('/', '*') => {
+                    depth += 1;
+                    self.read_char();
+                }
+                ('*', '/') => {
+                    depth -= 1;
+                    self.read_char();
+
+                    if depth == 0 {
+                        self.read_char();
+                        self.record_comment(start);
+                        return true;
+                    }
+                }
+                _ => {}
+            }
+
             self.read_char();
         }
     }
@@ -126,6 +463,7 @@ fn is_letter(ch: char) -> bool {
     ch.is_alphabetic() || ch == '_'
 }
 
+// Letters may come from any script, but numbers are ASCII only.
 fn is_digit(ch: char) -> bool {
-    ch.is_numeric()
+    ch.is_ascii_digit()
 }
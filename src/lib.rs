@@ -1,5 +1,11 @@
 pub mod ast;
+pub mod builtins;
+pub mod diagnostic;
+pub mod environment;
+pub mod evaluator;
+pub mod formatter;
 pub mod lexer;
+pub mod object;
 pub mod parser;
 pub mod repl;
 pub mod token;
@@ -1,7 +1,85 @@
+use std::{
+    env, fs,
+    io::{self, Write},
+    process, thread,
+};
+
 use colored::*;
-use mono::repl::start;
+use mono::{
+    ast::{self, Program},
+    diagnostic::Diagnostic,
+    environment::Environment,
+    evaluator::{eval, MAX_CALL_DEPTH},
+    formatter,
+    lexer::Lexer,
+    object::Object,
+    parser::Parser,
+    repl::start,
+    token::TokenType,
+};
+
+// Exit codes follow sysexits.h, as most interpreters do.
+const EXIT_USAGE: i32 = 64;
+const EXIT_PARSE_ERROR: i32 = 65;
+const EXIT_NO_INPUT: i32 = 66;
+const EXIT_RUNTIME_ERROR: i32 = 70;
+const EXIT_CANT_CREATE: i32 = 73;
+
+const USAGE: &str = "\
+usage: mono [command]
+
+commands:
+    repl                     start an interactive session (the default)
+    run <file>               run a script
+    -e <source>              run source given on the command line and print its value
+    tokens <file>            print the tokens of a script
+    ast <file>               print the syntax tree of a script
+    fmt [--check] <file>...  format scripts in place";
+
+// Evaluation recurses on the Rust stack. Each call takes a few kilobytes
+// of it in debug builds, so the main thread's is not enough.
+const STACK_SIZE: usize = MAX_CALL_DEPTH * 64 * 1024;
 
 fn main() {
+    let interpreter = thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(command)
+        .expect("could not start the interpreter thread");
+
+    // A panic has already been reported by the thread itself.
+    let status = interpreter.join().unwrap_or(101);
+
+    process::exit(status);
+}
+
+fn command() -> i32 {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args.as_slice() {
+        [] | ["repl"] => {
+            repl();
+            0
+        }
+        ["run", file] => run(file),
+        ["-e", source] => run_source("<expr>", source, true),
+        ["tokens", file] => tokens(file),
+        ["ast", file] => ast(file),
+        ["fmt", args @ ..] => fmt(args),
+        ["-h" | "--help" | "help"] => {
+            println!("{}", USAGE);
+            0
+        }
+        // `mono script.mono` is short for `mono run script.mono`.
+        [file] if !file.starts_with('-') && !matches!(*file, "run" | "tokens" | "ast") => run(file),
+        _ => {
+            eprintln!("{}", USAGE);
+            EXIT_USAGE
+        }
+    }
+}
+
+fn repl() {
     let greeting = "Welcome! This is the Mono (or monkeys, for the friends) programming language."
         .bright_green()
         .bold();
@@ -9,3 +87,147 @@ fn main() {
     println!("{}", "\nType some commands:\n".bold());
     start();
 }
+
+fn run(file: &str) -> i32 {
+    match read(file) {
+        Ok(source) => run_source(file, &source, false),
+        Err(status) => status,
+    }
+}
+
+// Runs a whole program. With `print_result` the value it evaluates to is
+// printed, unless it is null.
+fn run_source(name: &str, source: &str, print_result: bool) -> i32 {
+    let program = match parse(name, source) {
+        Ok(program) => program,
+        Err(status) => return status,
+    };
+
+    match eval(&program, &mut Environment::new()) {
+        Object::Error(error) => {
+            eprint!("{}", Diagnostic::runtime(&error).render(name, source));
+            EXIT_RUNTIME_ERROR
+        }
+        Object::Null => 0,
+        result => {
+            if print_result {
+                println!("{}", result.inspect());
+            }
+            0
+        }
+    }
+}
+
+fn tokens(file: &str) -> i32 {
+    let source = match read(file) {
+        Ok(source) => source,
+        Err(status) => return status,
+    };
+
+    // Output is often piped into `head`, so write errors just stop it.
+    let mut out = io::stdout().lock();
+    let mut lexer = Lexer::new(&source);
+    loop {
+        let token = lexer.next_token();
+        let line = writeln!(out, "{} {:?} {:?}", token.span, token.typ, token.literal);
+
+        if line.is_err() || token.typ == TokenType::EOF {
+            return 0;
+        }
+    }
+}
+
+fn ast(file: &str) -> i32 {
+    let source = match read(file) {
+        Ok(source) => source,
+        Err(status) => return status,
+    };
+
+    match parse(file, &source) {
+        Ok(program) => {
+            let _ = io::stdout()
+                .lock()
+                .write_all(ast::tree(&program).as_bytes());
+            0
+        }
+        Err(status) => status,
+    }
+}
+
+// `mono fmt [--check] <file>...` rewrites each file in canonical form.
+// With `--check` nothing is written and the exit code is 1 if any file
+// would change.
+fn fmt(args: &[&str]) -> i32 {
+    let check = args.contains(&"--check");
+    let files: Vec<&str> = args
+        .iter()
+        .copied()
+        .filter(|arg| *arg != "--check")
+        .collect();
+
+    if files.is_empty() {
+        eprintln!("usage: mono fmt [--check] <file>...");
+        return EXIT_USAGE;
+    }
+
+    // Every file is tried, and the most serious failure is reported.
+    let mut status = 0;
+
+    for file in files {
+        let source = match read(file) {
+            Ok(source) => source,
+            Err(error) => {
+                status = status.max(error);
+                continue;
+            }
+        };
+
+        let formatted = match formatter::format(&source) {
+            Ok(formatted) => formatted,
+            Err(diagnostics) => {
+                for diagnostic in diagnostics {
+                    eprint!("{}", diagnostic.render(file, &source));
+                }
+                status = status.max(EXIT_PARSE_ERROR);
+                continue;
+            }
+        };
+
+        if formatted == source {
+            continue;
+        }
+
+        if check {
+            println!("{} is not formatted", file);
+            status = status.max(1);
+        } else if let Err(e) = fs::write(file, formatted) {
+            eprintln!("{}: {}", file, e);
+            status = status.max(EXIT_CANT_CREATE);
+        }
+    }
+
+    status
+}
+
+fn read(file: &str) -> Result<String, i32> {
+    fs::read_to_string(file).map_err(|e| {
+        eprintln!("{}: {}", file, e);
+        EXIT_NO_INPUT
+    })
+}
+
+// Parses a program, printing every error found in it.
+fn parse(name: &str, source: &str) -> Result<Program, i32> {
+    let mut parser = Parser::new(Lexer::new(source));
+    let program = parser.parse_program();
+
+    if parser.errors().is_empty() {
+        return Ok(program);
+    }
+
+    for diagnostic in parser.errors() {
+        eprint!("{}", diagnostic.render(name, source));
+    }
+
+    Err(EXIT_PARSE_ERROR)
+}
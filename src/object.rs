@@ -0,0 +1,201 @@
+use std::{collections::BTreeMap, fmt, rc::Rc};
+
+#[cfg(feature = "bigint")]
+use num_bigint::BigInt;
+
+use crate::{
+    ast::{quote_string, BlockStatement, Identifier, Node},
+    environment::Environment,
+    token::Span,
+};
+
+// A native function. Returning `Err` turns the message into a Mono error.
+pub type BuiltinFunction = fn(&[Object]) -> Result<Object, String>;
+
+#[derive(Debug, Clone)]
+pub enum Object {
+    Integer(i64),
+    // Only holds values outside the i64 range, see `Object::big_integer`.
+    #[cfg(feature = "bigint")]
+    BigInteger(BigInt),
+    Float(f64),
+    Boolean(bool),
+    String(String),
+    Null,
+    ReturnValue(Box<Object>),
+    Error(RuntimeError),
+    Function(Rc<Function>),
+    Array(Vec<Object>),
+    Hash(BTreeMap<HashKey, Object>),
+    Builtin(Builtin),
+}
+
+impl Object {
+    // An error that has not been tied to a place in the source yet.
+    // The evaluator fills in the span of the node that produced it.
+    pub fn error(message: impl Into<String>) -> Object {
+        Object::Error(RuntimeError {
+            message: message.into(),
+            span: None,
+        })
+    }
+
+    // Integers that fit in an i64 are always stored as `Integer`.
+    #[cfg(feature = "bigint")]
+    pub fn big_integer(value: BigInt) -> Object {
+        match i64::try_from(&value) {
+            Ok(value) => Object::Integer(value),
+            Err(_) => Object::BigInteger(value),
+        }
+    }
+
+    #[cfg(feature = "bigint")]
+    pub fn to_big_integer(&self) -> Option<BigInt> {
+        match self {
+            Object::Integer(value) => Some(BigInt::from(*value)),
+            Object::BigInteger(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn inspect(&self) -> String {
+        match self {
+            Object::Integer(value) => value.to_string(),
+            #[cfg(feature = "bigint")]
+            Object::BigInteger(value) => value.to_string(),
+            // Always keeps a `.0` or exponent, so floats never look like integers.
+            Object::Float(value) => format!("{:?}", value),
+            Object::Boolean(value) => value.to_string(),
+            // Strings are shown the way they would be written in source.
+            Object::String(value) => quote_string(value),
+            Object::Null => "null".to_string(),
+            Object::ReturnValue(value) => value.inspect(),
+            Object::Error(error) => format!("ERROR: {}", error),
+            Object::Function(function) => function.inspect(),
+            Object::Array(elements) => {
+                let elements: Vec<String> = elements.iter().map(|e| e.inspect()).collect();
+
+                format!("[{}]", elements.join(", "))
+            }
+            Object::Hash(pairs) => {
+                let pairs: Vec<String> = pairs
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key.inspect(), value.inspect()))
+                    .collect();
+
+                format!("{{{}}}", pairs.join(", "))
+            }
+            Object::Builtin(builtin) => format!("builtin function {}", builtin.name),
+        }
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Object::Integer(_) => "INTEGER",
+            #[cfg(feature = "bigint")]
+            Object::BigInteger(_) => "INTEGER",
+            Object::Float(_) => "FLOAT",
+            Object::Boolean(_) => "BOOLEAN",
+            Object::String(_) => "STRING",
+            Object::Null => "NULL",
+            Object::ReturnValue(_) => "RETURN_VALUE",
+            Object::Error(_) => "ERROR",
+            Object::Function(_) => "FUNCTION",
+            Object::Array(_) => "ARRAY",
+            Object::Hash(_) => "HASH",
+            Object::Builtin(_) => "BUILTIN",
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self, Object::Error(_))
+    }
+
+    // Everything except `null` and `false` is truthy.
+    pub fn is_truthy(&self) -> bool {
+        !matches!(self, Object::Null | Object::Boolean(false))
+    }
+
+    // Only integers, booleans and strings can be used as hash keys.
+    // Floats are left out since NaN is not equal to itself.
+    pub fn hash_key(&self) -> Option<HashKey> {
+        match self {
+            Object::Integer(value) => Some(HashKey::Integer(*value)),
+            #[cfg(feature = "bigint")]
+            Object::BigInteger(value) => Some(HashKey::BigInteger(value.clone())),
+            Object::Boolean(value) => Some(HashKey::Boolean(*value)),
+            Object::String(value) => Some(HashKey::String(value.clone())),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RuntimeError {
+    pub message: String,
+    pub span: Option<Span>,
+}
+
+impl fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.span {
+            Some(span) => write!(f, "{}: {}", span, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Function {
+    pub parameters: Vec<Identifier>,
+    pub body: BlockStatement,
+    pub env: Environment,
+}
+
+impl Function {
+    pub fn inspect(&self) -> String {
+        let parameters: Vec<String> = self.parameters.iter().map(|p| p.string()).collect();
+
+        format!("fn({}) {}", parameters.join(", "), self.body.string())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Builtin {
+    pub name: String,
+    pub arity: Arity,
+    pub function: BuiltinFunction,
+}
+
+// How many arguments a builtin accepts. It is checked before the
+// function is called, so builtins can index their arguments freely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+    Exact(usize),
+    Any,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum HashKey {
+    Integer(i64),
+    #[cfg(feature = "bigint")]
+    BigInteger(BigInt),
+    Boolean(bool),
+    String(String),
+}
+
+impl HashKey {
+    pub fn inspect(&self) -> String {
+        self.to_object().inspect()
+    }
+
+    pub fn to_object(&self) -> Object {
+        match self {
+            HashKey::Integer(value) => Object::Integer(*value),
+            #[cfg(feature = "bigint")]
+            HashKey::BigInteger(value) => Object::BigInteger(value.clone()),
+            HashKey::Boolean(value) => Object::Boolean(*value),
+            HashKey::String(value) => Object::String(value.clone()),
+        }
+    }
+}
@@ -1,20 +1,36 @@
-use std::{collections::HashMap, os::linux::raw::stat};
+use std::{
+    collections::HashMap,
+    num::{IntErrorKind, ParseIntError},
+};
+
+#[cfg(feature = "bigint")]
+use num_bigint::BigInt;
+
+#[cfg(feature = "bigint")]
+use crate::ast::BigIntegerLiteral;
 
 use crate::{
     ast::{
-        BlockStatement, Boolean, ExpressionStatement, ExpressionVariant, ExpressionVariants,
-        Identifier, IfExpression, InfixExpression, IntegerLiteral, LetStatement, PrefixExpression,
-        Program, ReturnStatement, StatementVariant,
+        ArrayLiteral, BlockStatement, Boolean, CallExpression, ExpressionStatement,
+        ExpressionVariant, ExpressionVariants, FloatLiteral, FunctionLiteral, HashLiteral,
+        Identifier, IfExpression, IndexExpression, InfixExpression, IntegerLiteral, LetStatement,
+        Node, PrefixExpression, Program, ReturnStatement, StatementVariant, StringLiteral,
     },
+    diagnostic::Diagnostic,
     lexer::Lexer,
-    token::{self, Token, TokenType},
+    token::{Span, Token, TokenType},
 };
 
 pub struct Parser {
     lexer: Lexer,
     current_token: Token,
     peek_token: Token,
-    errors: Vec<String>,
+    // Doc comments found right before the current and peek tokens.
+    current_docs: Vec<String>,
+    peek_docs: Vec<String>,
+    // Braces opened before the current token and not closed yet.
+    brace_depth: usize,
+    errors: Vec<Diagnostic>,
 
     prefix_parse_fns: HashMap<TokenType, PrefixParseFn>,
     infix_parse_fns: HashMap<TokenType, InfixParseFn>,
@@ -26,6 +42,9 @@ impl Parser {
             lexer,
             current_token: Token::new(TokenType::ILLEGAL, ' '),
             peek_token: Token::new(TokenType::ILLEGAL, ' '),
+            current_docs: Vec::new(),
+            peek_docs: Vec::new(),
+            brace_depth: 0,
             errors: Vec::new(),
             prefix_parse_fns: HashMap::new(),
             infix_parse_fns: HashMap::new(),
@@ -37,6 +56,8 @@ impl Parser {
         // Register prefix parse functions
         parser.register_prefix(TokenType::IDENT, Parser::parse_identifier);
         parser.register_prefix(TokenType::INT, Parser::parse_integer_literal);
+        parser.register_prefix(TokenType::FLOAT, Parser::parse_float_literal);
+        parser.register_prefix(TokenType::STRING, Parser::parse_string_literal);
         parser.register_prefix(TokenType::BANG, Parser::parse_prefix_expression);
         parser.register_prefix(TokenType::MINUS, Parser::parse_prefix_expression);
         // Boolean parse functions
@@ -46,6 +67,11 @@ impl Parser {
         parser.register_prefix(TokenType::LPAREN, Parser::parse_grouped_expressions);
 
         parser.register_prefix(TokenType::IF, Parser::parse_if_expression);
+        parser.register_prefix(TokenType::FUNCTION, Parser::parse_function_literal);
+        parser.register_prefix(TokenType::LBRACKET, Parser::parse_array_literal);
+        // Blocks are only ever parsed directly by `if` and `fn`, so a brace
+        // reached through parse_expression always opens a hash literal.
+        parser.register_prefix(TokenType::LBRACE, Parser::parse_hash_literal);
 
         // Register infix parse functions
         parser.register_infix(TokenType::PLUS, Parser::parse_infix_expression);
@@ -56,6 +82,14 @@ impl Parser {
         parser.register_infix(TokenType::NEQ, Parser::parse_infix_expression);
         parser.register_infix(TokenType::LT, Parser::parse_infix_expression);
         parser.register_infix(TokenType::GT, Parser::parse_infix_expression);
+        parser.register_infix(TokenType::LTE, Parser::parse_infix_expression);
+        parser.register_infix(TokenType::GTE, Parser::parse_infix_expression);
+        parser.register_infix(TokenType::PERCENT, Parser::parse_infix_expression);
+        parser.register_infix(TokenType::POWER, Parser::parse_infix_expression);
+        parser.register_infix(TokenType::AND, Parser::parse_infix_expression);
+        parser.register_infix(TokenType::OR, Parser::parse_infix_expression);
+        parser.register_infix(TokenType::LPAREN, Parser::parse_call_expression);
+        parser.register_infix(TokenType::LBRACKET, Parser::parse_index_expression);
 
         parser
     }
@@ -70,12 +104,15 @@ impl Parser {
             condition: Box::new(ExpressionVariants::Ident(Identifier {
                 token: self.current_token.clone(),
                 value: "".to_string(),
+                span: self.current_token.span,
             })),
             consequence: BlockStatement {
                 token: self.current_token.clone(),
                 statements: Vec::new(),
+                span: self.current_token.span,
             },
             alternative: None,
+            span: self.current_token.span,
         };
 
         if !self.expect_peek(TokenType::LPAREN) {
@@ -83,8 +120,7 @@ impl Parser {
         }
 
         self.next_token();
-        // TODO: Optimize this
-        expression.condition = Box::new(self.parse_expression(Precedence::LOWEST.index()).unwrap());
+        expression.condition = Box::new(self.parse_expression(Precedence::LOWEST.index())?);
 
         if !self.expect_peek(TokenType::RPAREN) {
             return None;
@@ -96,27 +132,245 @@ impl Parser {
 
         expression.consequence = self.parse_block_statement();
 
+        if self.peek_token_is(TokenType::ELSE) {
+            self.next_token();
+
+            expression.alternative = if self.peek_token_is(TokenType::IF) {
+                // An `else if` is stored as an alternative block holding
+                // a single nested if expression.
+                self.next_token();
+                let token = self.current_token.clone();
+                let nested = self.parse_if_expression()?;
+                let span = nested.span();
+
+                Some(BlockStatement {
+                    token: token.clone(),
+                    statements: vec![StatementVariant::Expression(ExpressionStatement {
+                        token,
+                        expression: Some(nested),
+                        span,
+                    })],
+                    span,
+                })
+            } else {
+                if !self.expect_peek(TokenType::LBRACE) {
+                    return None;
+                }
+
+                Some(self.parse_block_statement())
+            };
+        }
+
+        expression.span = self.span_from(expression.span);
+
         Some(ExpressionVariants::If(expression))
     }
 
+    fn parse_function_literal(&mut self) -> ExpressionVariant {
+        let token = self.current_token.clone();
+
+        if !self.expect_peek(TokenType::LPAREN) {
+            return None;
+        }
+
+        let parameters = self.parse_function_parameters()?;
+
+        if !self.expect_peek(TokenType::LBRACE) {
+            return None;
+        }
+
+        let body = self.parse_block_statement();
+
+        Some(ExpressionVariants::Function(FunctionLiteral {
+            span: self.span_from(token.span),
+            token,
+            parameters,
+            body,
+        }))
+    }
+
+    fn parse_function_parameters(&mut self) -> Option<Vec<Identifier>> {
+        let mut identifiers = Vec::new();
+
+        if self.peek_token_is(TokenType::RPAREN) {
+            self.next_token();
+            return Some(identifiers);
+        }
+
+        if !self.expect_peek(TokenType::IDENT) {
+            return None;
+        }
+
+        identifiers.push(Identifier {
+            token: self.current_token.clone(),
+            value: self.current_token.literal.clone(),
+            span: self.current_token.span,
+        });
+
+        while self.peek_token_is(TokenType::COMMA) {
+            self.next_token();
+
+            if !self.expect_peek(TokenType::IDENT) {
+                return None;
+            }
+
+            identifiers.push(Identifier {
+                token: self.current_token.clone(),
+                value: self.current_token.literal.clone(),
+                span: self.current_token.span,
+            });
+        }
+
+        if !self.expect_peek(TokenType::RPAREN) {
+            return None;
+        }
+
+        Some(identifiers)
+    }
+
+    fn parse_call_expression(&mut self, function: ExpressionVariants) -> ExpressionVariant {
+        let token = self.current_token.clone();
+        let arguments = self.parse_expression_list(TokenType::RPAREN)?;
+
+        Some(ExpressionVariants::Call(CallExpression {
+            token,
+            span: self.span_from(function.span()),
+            function: Box::new(function),
+            arguments,
+        }))
+    }
+
+    fn parse_array_literal(&mut self) -> ExpressionVariant {
+        let token = self.current_token.clone();
+        let elements = self.parse_expression_list(TokenType::RBRACKET)?;
+
+        Some(ExpressionVariants::Array(ArrayLiteral {
+            span: self.span_from(token.span),
+            token,
+            elements,
+        }))
+    }
+
+    fn parse_hash_literal(&mut self) -> ExpressionVariant {
+        let token = self.current_token.clone();
+        let mut pairs = Vec::new();
+
+        while !self.peek_token_is(TokenType::RBRACE) {
+            self.next_token();
+            let key = self.parse_expression(Precedence::LOWEST.index())?;
+
+            if !self.expect_peek(TokenType::COLON) {
+                return None;
+            }
+
+            self.next_token();
+            let value = self.parse_expression(Precedence::LOWEST.index())?;
+
+            pairs.push((key, value));
+
+            if !self.peek_token_is(TokenType::RBRACE) && !self.expect_peek(TokenType::COMMA) {
+                return None;
+            }
+        }
+
+        if !self.expect_peek(TokenType::RBRACE) {
+            return None;
+        }
+
+        Some(ExpressionVariants::Hash(HashLiteral {
+            span: self.span_from(token.span),
+            token,
+            pairs,
+        }))
+    }
+
+    fn parse_index_expression(&mut self, left: ExpressionVariants) -> ExpressionVariant {
+        let token = self.current_token.clone();
+
+        self.next_token();
+        let index = self.parse_expression(Precedence::LOWEST.index())?;
+
+        if !self.expect_peek(TokenType::RBRACKET) {
+            return None;
+        }
+
+        Some(ExpressionVariants::Index(IndexExpression {
+            token,
+            span: self.span_from(left.span()),
+            left: Box::new(left),
+            index: Box::new(index),
+        }))
+    }
+
+    // Parses comma separated expressions up to the `end` token,
+    // as found in call arguments and array literals.
+    fn parse_expression_list(&mut self, end: TokenType) -> Option<Vec<ExpressionVariants>> {
+        let mut list = Vec::new();
+
+        if self.peek_token_is(end) {
+            self.next_token();
+            return Some(list);
+        }
+
+        self.next_token();
+        list.push(self.parse_expression(Precedence::LOWEST.index())?);
+
+        while self.peek_token_is(TokenType::COMMA) {
+            self.next_token();
+            self.next_token();
+            list.push(self.parse_expression(Precedence::LOWEST.index())?);
+        }
+
+        if !self.expect_peek(end) {
+            return None;
+        }
+
+        Some(list)
+    }
+
     fn parse_block_statement(&mut self) -> BlockStatement {
         let mut block = BlockStatement {
             token: self.current_token.clone(),
             statements: Vec::new(),
+            span: self.current_token.span,
         };
 
         self.next_token();
 
         while !self.current_token_is(TokenType::RBRACE) && !self.current_token_is(TokenType::EOF) {
+            let (errors, depth) = (self.errors.len(), self.brace_depth);
             let statement = self.parse_statement();
 
             if let Some(p) = statement {
                 block.statements.push(p);
             }
 
+            if self.errors.len() > errors {
+                self.synchronize(depth);
+
+                // Recovery stopped on the `}` closing this block.
+                if self.current_token_is(TokenType::RBRACE) && self.brace_depth == depth {
+                    break;
+                }
+            }
+
             self.next_token();
         }
 
+        if !self.current_token_is(TokenType::RBRACE) {
+            let diagnostic = Diagnostic::error(
+                "E0001",
+                "Expected next token to be RBRACE, got EOF instead".to_string(),
+                self.current_token.span,
+            )
+            .with_label("expected RBRACE here")
+            .with_secondary(block.span, "block opened here");
+
+            self.errors.push(diagnostic);
+        }
+
+        block.span = self.span_from(block.span);
+
         block
     }
 
@@ -129,13 +383,22 @@ impl Parser {
             return None;
         }
 
-        return expression;
+        expression
     }
 
     fn parse_boolean(&mut self) -> Option<ExpressionVariants> {
         Some(ExpressionVariants::Boolean(Boolean {
             token: self.current_token.clone(),
             value: self.current_token_is(TokenType::TRUE),
+            span: self.current_token.span,
+        }))
+    }
+
+    fn parse_string_literal(&mut self) -> Option<ExpressionVariants> {
+        Some(ExpressionVariants::String(StringLiteral {
+            token: self.current_token.clone(),
+            value: self.current_token.literal.clone(),
+            span: self.current_token.span,
         }))
     }
 
@@ -143,24 +406,33 @@ impl Parser {
         Some(ExpressionVariants::Ident(Identifier {
             token: self.current_token.clone(),
             value: self.current_token.literal.clone(),
+            span: self.current_token.span,
         }))
     }
 
     pub fn parse_program(&mut self) -> Program {
         let mut program = Program {
             statements: Vec::new(),
+            span: self.current_token.span,
         };
 
         while !self.current_token_is(TokenType::EOF) {
+            let (errors, depth) = (self.errors.len(), self.brace_depth);
             let statement = self.parse_statement();
 
             if let Some(statement_variant) = statement {
                 program.statements.push(statement_variant);
             }
 
+            if self.errors.len() > errors {
+                self.synchronize(depth);
+            }
+
             self.next_token();
         }
 
+        program.span = self.span_from(program.span);
+
         program
     }
 
@@ -177,13 +449,14 @@ impl Parser {
         let mut statement = ExpressionStatement {
             token: self.current_token.clone(),
             expression: None,
+            span: self.current_token.span,
         };
 
+        let errors = self.errors.len();
         statement.expression = self.parse_expression(Precedence::LOWEST.index());
+        self.skip_semicolon(errors);
 
-        if self.peek_token_is(TokenType::SEMICOLON) {
-            self.next_token();
-        }
+        statement.span = self.span_from(statement.span);
 
         Some(StatementVariant::Expression(statement))
     }
@@ -191,7 +464,7 @@ impl Parser {
     fn parse_expression(&mut self, precedence: usize) -> Option<ExpressionVariants> {
         if !self.prefix_parse_fns.contains_key(&self.current_token.typ) {
             self.no_prefix_parse_fn_error(self.current_token.typ);
-            return None;
+            None
         } else {
             let mut left_exp = self.prefix_parse_fns[&self.current_token.typ](self);
 
@@ -215,20 +488,28 @@ impl Parser {
         let mut expression = InfixExpression {
             token: self.current_token.clone(),
             operator: self.current_token.literal.clone(),
+            span: left.span(),
             left: Box::new(left),
             right: Box::new(ExpressionVariants::Ident(Identifier {
                 token: Token::new(TokenType::ASTERISK, ';'),
                 value: "none".to_string(),
+                span: Span::default(),
             })),
         };
 
-        let precedence = self.current_precedence();
+        let mut precedence = self.current_precedence();
+        // `**` is right associative: `2 ** 3 ** 2` is `2 ** (3 ** 2)`.
+        if self.current_token_is(TokenType::POWER) {
+            precedence -= 1;
+        }
 
         self.next_token();
         if let Some(exp) = self.parse_expression(precedence) {
             expression.right = Box::new(exp);
         }
 
+        expression.span = self.span_from(expression.span);
+
         Some(ExpressionVariants::Infix(expression))
     }
 
@@ -240,31 +521,62 @@ impl Parser {
             right: Box::new(ExpressionVariants::Ident(Identifier {
                 token: Token::new(TokenType::ASTERISK, ';'),
                 value: "none".to_string(),
+                span: Span::default(),
             })),
+            span: self.current_token.span,
         };
 
         self.next_token();
 
+        // The digits of i64::MIN alone are past i64::MAX, so a minus
+        // right before them is read as part of the literal. Operators
+        // that bind tighter than `-`, such as `**`, keep applying first.
+        if expression.operator == "-"
+            && self.current_token_is(TokenType::INT)
+            && self.peek_preference() <= Precedence::PREFIX.index()
+        {
+            if let Some(literal) = self.parse_min_integer_literal(expression.span) {
+                return Some(literal);
+            }
+        }
+
         //expression.right = self.parse_expression(Precedence::PREFIX);
         if let Some(ex) = self.parse_expression(Precedence::PREFIX.index()) {
             expression.right = Box::new(ex)
         }
+
+        expression.span = self.span_from(expression.span);
         Some(ExpressionVariants::Prefix(expression))
     }
 
     fn parse_return_statement(&mut self) -> Option<StatementVariant> {
-        let statement = ReturnStatement {
+        let mut statement = ReturnStatement {
             token: self.current_token.clone(),
             return_value: None,
+            span: self.current_token.span,
         };
 
-        self.next_token();
-
-        // We are skipping expressions until we encounter a semicolon
-        while !self.current_token_is(TokenType::SEMICOLON) {
+        // A bare `return;` has no value.
+        if self.peek_token_is(TokenType::SEMICOLON) {
             self.next_token();
+            statement.span = self.span_from(statement.span);
+
+            return Some(StatementVariant::Return(statement));
+        }
+
+        // Nor does one that ends the block or the input.
+        if self.peek_token_is(TokenType::RBRACE) || self.peek_token_is(TokenType::EOF) {
+            return Some(StatementVariant::Return(statement));
         }
 
+        self.next_token();
+
+        let errors = self.errors.len();
+        statement.return_value = self.parse_expression(Precedence::LOWEST.index());
+        self.skip_semicolon(errors);
+
+        statement.span = self.span_from(statement.span);
+
         Some(StatementVariant::Return(statement))
     }
 
@@ -274,10 +586,17 @@ impl Parser {
             name: Identifier {
                 token: self.current_token.clone(),
                 value: String::new(),
+                span: self.current_token.span,
             },
             value: None,
+            doc: None,
+            span: self.current_token.span,
         };
 
+        if !self.current_docs.is_empty() {
+            statement.doc = Some(self.current_docs.join("\n"));
+        }
+
         if !self.expect_peek(TokenType::IDENT) {
             return None;
         }
@@ -285,6 +604,7 @@ impl Parser {
         statement.name = Identifier {
             token: self.current_token.clone(),
             value: self.current_token.literal.clone(),
+            span: self.current_token.span,
         };
 
         if !self.expect_peek(TokenType::ASSIGN) {
@@ -295,6 +615,7 @@ impl Parser {
 
         // Current token is now <expr> in
         // let <ident> = <expr>
+        let errors = self.errors.len();
         statement.value = self.parse_expression(Precedence::LOWEST.index());
 
         // We are skipping the expressions
@@ -304,9 +625,9 @@ impl Parser {
             self.next_token();
         }
         */
-        if self.peek_token_is(TokenType::SEMICOLON) {
-            self.next_token();
-        }
+        self.skip_semicolon(errors);
+
+        statement.span = self.span_from(statement.span);
 
         Some(StatementVariant::Let(statement))
     }
@@ -315,21 +636,92 @@ impl Parser {
         let mut literal = IntegerLiteral {
             token: self.current_token.clone(),
             value: 0,
+            span: self.current_token.span,
         };
 
-        // Parse the string as an integer
-        // Handle parsing error instead of default to 0!
-        let value = self.current_token.literal.parse::<i64>();
-
-        match value {
+        match parse_integer(&self.current_token.literal) {
             Ok(v) => {
                 literal.value = v;
 
-                return Some(ExpressionVariants::Integer(literal));
+                Some(ExpressionVariants::Integer(literal))
+            }
+            // Literals past i64::MAX become big integers.
+            #[cfg(feature = "bigint")]
+            Err(e) if e.kind() == &IntErrorKind::PosOverflow => {
+                let value = parse_big_integer(&self.current_token.literal)?;
+
+                Some(ExpressionVariants::BigInteger(BigIntegerLiteral {
+                    token: self.current_token.clone(),
+                    value,
+                    span: self.current_token.span,
+                }))
             }
             Err(e) => {
-                eprintln!("Parse error: {}, found {}", e, self.current_token.literal);
-                return None;
+                let message = format!(
+                    "Could not parse {} as an integer: {}",
+                    self.current_token.literal, e
+                );
+                let diagnostic = Diagnostic::error("E0004", message, self.current_token.span);
+                let diagnostic = match e.kind() {
+                    IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => diagnostic
+                        .with_label("integer literal out of range")
+                        .with_note(format!(
+                            "integers must be between {} and {}",
+                            i64::MIN,
+                            i64::MAX
+                        )),
+                    _ => diagnostic.with_label("invalid integer literal"),
+                };
+
+                self.errors.push(diagnostic);
+
+                None
+            }
+        }
+    }
+
+    // Reads `-` and the current integer token as one literal, when
+    // together they are i64::MIN.
+    fn parse_min_integer_literal(&self, minus: Span) -> Option<ExpressionVariants> {
+        let (digits, radix) = integer_digits(&self.current_token.literal);
+        let value = i64::from_str_radix(&format!("-{}", digits), radix).ok()?;
+
+        if value != i64::MIN {
+            return None;
+        }
+
+        let span = minus.to(self.current_token.span);
+
+        Some(ExpressionVariants::Integer(IntegerLiteral {
+            token: Token {
+                typ: TokenType::INT,
+                literal: format!("-{}", self.current_token.literal),
+                span,
+            },
+            value,
+            span,
+        }))
+    }
+
+    fn parse_float_literal(&mut self) -> Option<ExpressionVariants> {
+        match self.current_token.literal.replace('_', "").parse::<f64>() {
+            Ok(value) if value.is_finite() => Some(ExpressionVariants::Float(FloatLiteral {
+                token: self.current_token.clone(),
+                value,
+                span: self.current_token.span,
+            })),
+            _ => {
+                let message = format!(
+                    "Could not parse {} as a float: number too large",
+                    self.current_token.literal
+                );
+                let diagnostic = Diagnostic::error("E0005", message, self.current_token.span)
+                    .with_label("float literal out of range")
+                    .with_note(format!("floats can be at most {:e}", f64::MAX));
+
+                self.errors.push(diagnostic);
+
+                None
             }
         }
     }
@@ -338,9 +730,8 @@ impl Parser {
     // Error functions.
     /////////////////////
 
-    pub fn errors(&self) -> Vec<String> {
-        //TODO: This is not optimal...
-        self.errors.clone()
+    pub fn errors(&self) -> &[Diagnostic] {
+        &self.errors
     }
 
     fn peek_error(&mut self, token: TokenType) {
@@ -348,23 +739,120 @@ impl Parser {
             "Expected next token to be {:?}, got {:?} instead",
             token, self.peek_token.typ
         );
-        self.errors.push(message);
+
+        let diagnostic = Diagnostic::error("E0001", message, self.peek_token.span)
+            .with_label(format!("expected {:?} here", token));
+
+        self.errors.push(diagnostic);
     }
 
     fn no_prefix_parse_fn_error(&mut self, token_type: TokenType) {
-        self.errors.push(format!(
-            "No prefix parse function for {:?} found",
-            token_type,
-        ));
+        let span = self.current_token.span;
+
+        let diagnostic = if token_type == TokenType::ILLEGAL {
+            let diagnostic = Diagnostic::error(
+                "E0003",
+                format!("Illegal token {}", self.current_token.literal),
+                span,
+            )
+            .with_label("not valid Mono");
+
+            if self.current_token.literal == "/*" {
+                diagnostic.with_help("block comments must be closed with `*/`")
+            } else if self.current_token.literal.starts_with('"') {
+                diagnostic.with_help(
+                    "strings must be closed with `\"` and may only use the escapes \\n, \\t, \\r, \\\", \\\\ and \\u{...}",
+                )
+            } else {
+                diagnostic
+            }
+        } else {
+            Diagnostic::error(
+                "E0002",
+                format!("No prefix parse function for {:?} found", token_type),
+                span,
+            )
+            .with_label("expected an expression")
+        };
+
+        self.errors.push(diagnostic);
     }
 
     //////////////////////
     // Utility functions.
     //////////////////////
 
+    // Skips the rest of a statement that failed to parse, so that one
+    // mistake is reported once instead of cascading into the statements
+    // after it. Stops on a `;` or a closing `}`, or right before a `let`,
+    // `return` or the `}` that ends the enclosing block. Braces the
+    // statement opened, counted from `start`, the depth it began at, are
+    // skipped as a whole, even those read before the error was found.
+    // When the error is on the enclosing block's `}`, it is left as the
+    // current token for the block to close on.
+    fn synchronize(&mut self, start: usize) {
+        while !self.current_token_is(TokenType::EOF) {
+            let depth = match self.current_token.typ {
+                TokenType::LBRACE => self.brace_depth + 1,
+                TokenType::RBRACE => self.brace_depth.saturating_sub(1),
+                _ => self.brace_depth,
+            };
+
+            if self.current_token_is(TokenType::RBRACE) && depth < start {
+                return;
+            }
+
+            if depth <= start {
+                if self.current_token_is(TokenType::SEMICOLON) {
+                    return;
+                }
+
+                // A failed `if` goes on through its `else` branch.
+                if self.current_token_is(TokenType::RBRACE) && !self.peek_token_is(TokenType::ELSE)
+                {
+                    if self.peek_token_is(TokenType::SEMICOLON) {
+                        self.next_token();
+                    }
+
+                    return;
+                }
+
+                if matches!(
+                    self.peek_token.typ,
+                    TokenType::LET | TokenType::RETURN | TokenType::RBRACE | TokenType::EOF
+                ) {
+                    return;
+                }
+            }
+
+            self.next_token();
+        }
+    }
+
+    // Steps onto the `;` ending a statement. After an error it is left
+    // for `synchronize`, which has to see any `}` that comes first.
+    fn skip_semicolon(&mut self, errors: usize) {
+        if self.errors.len() == errors && self.peek_token_is(TokenType::SEMICOLON) {
+            self.next_token();
+        }
+    }
+
+    // The span from `start` up to the end of the current token.
+    fn span_from(&self, start: Span) -> Span {
+        start.to(self.current_token.span)
+    }
+
     fn next_token(&mut self) {
+        match self.current_token.typ {
+            TokenType::LBRACE => self.brace_depth += 1,
+            TokenType::RBRACE => self.brace_depth = self.brace_depth.saturating_sub(1),
+            _ => {}
+        }
+
         self.current_token = self.peek_token.clone();
+        self.current_docs = std::mem::take(&mut self.peek_docs);
         self.peek_token = self.lexer.next_token();
+        self.peek_docs = self.lexer.take_doc_comments();
     }
 
     fn current_token_is(&self, token_type: TokenType) -> bool {
@@ -380,7 +868,7 @@ impl Parser {
             self.next_token();
             true
         } else {
-            self.peek_error(token_type.clone());
+            self.peek_error(token_type);
             false
         }
     }
@@ -414,18 +902,52 @@ impl Parser {
     }
 }
 
+// Parses an integer literal as written in source: decimal, or hex, octal
+// and binary with a `0x`, `0o` or `0b` prefix, with any `_` separators.
+fn parse_integer(literal: &str) -> Result<i64, ParseIntError> {
+    let (digits, radix) = integer_digits(literal);
+
+    i64::from_str_radix(&digits, radix)
+}
+
+#[cfg(feature = "bigint")]
+fn parse_big_integer(literal: &str) -> Option<BigInt> {
+    let (digits, radix) = integer_digits(literal);
+
+    BigInt::parse_bytes(digits.as_bytes(), radix)
+}
+
+// Splits a literal into its digits and radix.
+fn integer_digits(literal: &str) -> (String, u32) {
+    let digits = literal.replace('_', "");
+    let prefix = digits.get(..2).map(str::to_ascii_lowercase);
+
+    let radix = match prefix.as_deref() {
+        Some("0x") => 16,
+        Some("0o") => 8,
+        Some("0b") => 2,
+        _ => return (digits, 10),
+    };
+
+    (digits[2..].to_string(), radix)
+}
+
 type PrefixParseFn = fn(&mut Parser) -> ExpressionVariant;
 type InfixParseFn = fn(&mut Parser, ExpressionVariants) -> ExpressionVariant;
 
 #[derive(Clone, Copy, Debug)]
 pub enum Precedence {
     LOWEST,
+    OR,          // ||
+    AND,         // &&
     EQUALS,      // ==
     LESSGREATER, // > OR <
     SUM,         // +
-    PRODUCT,     // *
+    PRODUCT,     // * / %
     PREFIX,      // -X or !X
+    EXPONENT,    // X ** Y
     CALL,        // myFunction(X)
+    INDEX,       // array[index]
 }
 
 impl Precedence {
@@ -440,9 +962,17 @@ pub fn precedences() -> HashMap<TokenType, Precedence> {
         (TokenType::NEQ, Precedence::EQUALS),
         (TokenType::LT, Precedence::LESSGREATER),
         (TokenType::GT, Precedence::LESSGREATER),
+        (TokenType::LTE, Precedence::LESSGREATER),
+        (TokenType::GTE, Precedence::LESSGREATER),
+        (TokenType::AND, Precedence::AND),
+        (TokenType::OR, Precedence::OR),
         (TokenType::PLUS, Precedence::SUM),
         (TokenType::MINUS, Precedence::SUM),
         (TokenType::SLASH, Precedence::PRODUCT),
         (TokenType::ASTERISK, Precedence::PRODUCT),
+        (TokenType::PERCENT, Precedence::PRODUCT),
+        (TokenType::POWER, Precedence::EXPONENT),
+        (TokenType::LPAREN, Precedence::CALL),
+        (TokenType::LBRACKET, Precedence::INDEX),
     ])
 }
@@ -1,23 +1,503 @@
+use std::{borrow::Cow, env, fs, path::PathBuf, time::Instant};
+
 use colored::Colorize;
-use text_io::read;
+use rustyline::{
+    completion::Completer,
+    error::ReadlineError,
+    highlight::{CmdKind, Highlighter},
+    hint::Hinter,
+    history::DefaultHistory,
+    validate::Validator,
+    Context, Editor,
+};
+
+use crate::{
+    ast::{self, Program},
+    builtins,
+    diagnostic::Diagnostic,
+    environment::Environment,
+    evaluator::eval,
+    lexer::Lexer,
+    object::Object,
+    parser::Parser,
+    token::{TokenType, KEYWORDS},
+};
+
+// Name used for the source of REPL input in diagnostics.
+const SOURCE_NAME: &str = "<repl>";
+
+const PROMPT: &str = "mono >> ";
+// Shown while an input spans several lines.
+const CONTINUATION_PROMPT: &str = "   ... ";
+
+const HISTORY_FILE: &str = ".mono_history";
+
+const HELP: &str = "\
+:tokens <source>   print the tokens of the source
+:ast <source>      print the syntax tree of the source
+:env               list the current bindings
+:load <file>       run a script in the current session
+:type <expr>       print the type of a value
+:time <expr>       evaluate and print how long it took
+:reset             drop every binding
+:help              print this message
+";
+
+// An interactive session. Bindings made by one input stay visible to the
+// ones that follow.
+pub struct Repl {
+    env: Environment,
+    // Every input evaluated so far, one after another. Each is lexed from
+    // where the previous one ended, so a span from a function defined in
+    // an earlier input still points at that input's text.
+    session: String,
+    // Where each input starts in `session`, with its name.
+    inputs: Vec<(usize, String)>,
+}
+
+impl Repl {
+    pub fn new() -> Self {
+        Self {
+            env: Environment::new(),
+            session: String::new(),
+            inputs: Vec::new(),
+        }
+    }
+
+    // Parses and evaluates one input, returning what should be printed
+    // for it: the inspect form of its value, or its errors. Input starting
+    // with `:` is a command to the REPL itself, such as `:help`.
+    pub fn eval(&mut self, input: &str) -> String {
+        if let Some(command) = input.trim_start().strip_prefix(':') {
+            return self.command(command);
+        }
+
+        self.eval_source(SOURCE_NAME, input)
+    }
+
+    // The bindings made so far.
+    pub fn environment(&self) -> &Environment {
+        &self.env
+    }
+
+    fn command(&mut self, command: &str) -> String {
+        let (name, argument) = match command.split_once(char::is_whitespace) {
+            Some((name, argument)) => (name, argument.trim()),
+            None => (command.trim(), ""),
+        };
+
+        match (name, argument) {
+            ("tokens", source) if !source.is_empty() => tokens(source),
+            ("ast", source) if !source.is_empty() => match parse(SOURCE_NAME, source) {
+                Ok(program) => ast::tree(&program),
+                Err(errors) => errors,
+            },
+            ("env", "") => self
+                .env
+                .bindings()
+                .iter()
+                .map(|(name, value)| format!("{} = {}\n", name, value.inspect()))
+                .collect(),
+            ("load", file) if !file.is_empty() => match fs::read_to_string(file) {
+                Ok(source) => self.eval_source(file, &source),
+                Err(e) => format!("{}: {}\n", file, e),
+            },
+            ("type", source) if !source.is_empty() => match self.value(SOURCE_NAME, source) {
+                Ok(value) => format!("{}\n", value.type_name()),
+                Err(errors) => errors,
+            },
+            ("time", source) if !source.is_empty() => {
+                let start = Instant::now();
+                let output = self.eval_source(SOURCE_NAME, source);
+                let elapsed = format!("took {:?}", start.elapsed());
+
+                format!("{}{}\n", output, elapsed.dimmed())
+            }
+            ("reset", "") => {
+                *self = Self::new();
+                String::new()
+            }
+            ("help", "") => HELP.to_string(),
+            ("tokens" | "ast" | "load" | "type" | "time" | "env" | "reset" | "help", _) => {
+                format!("wrong arguments for :{}, see :help\n", name)
+            }
+            _ => format!("unknown command :{}, see :help\n", name),
+        }
+    }
+
+    fn eval_source(&mut self, name: &str, source: &str) -> String {
+        match self.value(name, source) {
+            // Statements such as `let` evaluate to null; echoing it after
+            // each of them is just noise.
+            Ok(Object::Null) => String::new(),
+            Ok(result) => format!("{}\n", result.inspect()),
+            Err(errors) => errors,
+        }
+    }
+
+    // Evaluates `source`, or returns its parse or runtime errors rendered.
+    fn value(&mut self, name: &str, source: &str) -> Result<Object, String> {
+        let offset = self.session.len();
+        let mut parser = Parser::new(Lexer::with_offset(source, offset));
+        let program = parser.parse_program();
+
+        self.session.push_str(source);
+        if !source.ends_with('\n') {
+            self.session.push('\n');
+        }
+        self.inputs.push((offset, name.to_string()));
+
+        if !parser.errors().is_empty() {
+            let errors = parser.errors().iter().map(|d| self.render(d)).collect();
+
+            // Nothing in input that did not parse can be referred to later.
+            self.session.truncate(offset);
+            self.inputs.pop();
+
+            return Err(errors);
+        }
+
+        match eval(&program, &mut self.env) {
+            Object::Error(error) => Err(self.render(&Diagnostic::runtime(&error))),
+            value => Ok(value),
+        }
+    }
+
+    // Renders a diagnostic against the input its span points into.
+    fn render(&self, diagnostic: &Diagnostic) -> String {
+        let name = self
+            .inputs
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= diagnostic.span.start)
+            .map_or(SOURCE_NAME, |(_, name)| name.as_str());
+
+        diagnostic.render(name, &self.session)
+    }
+}
+
+impl Default for Repl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Parses a program, or renders every error found in it.
+fn parse(name: &str, source: &str) -> Result<Program, String> {
+    let mut parser = Parser::new(Lexer::new(source));
+    let program = parser.parse_program();
+
+    if parser.errors().is_empty() {
+        return Ok(program);
+    }
+
+    Err(parser
+        .errors()
+        .iter()
+        .map(|diagnostic| diagnostic.render(name, source))
+        .collect())
+}
+
+// One line per token, in the same form as `mono tokens`.
+fn tokens(source: &str) -> String {
+    let mut lexer = Lexer::new(source);
+    let mut out = String::new();
+
+    loop {
+        let token = lexer.next_token();
+        out.push_str(&format!(
+            "{} {:?} {:?}\n",
+            token.span, token.typ, token.literal
+        ));
+
+        if token.typ == TokenType::EOF {
+            return out;
+        }
+    }
+}
+
+// Completions for the word ending at `pos` in `line`: the byte offset
+// where that word starts and every candidate that could replace it.
+// Keywords, builtins and bound names are offered, or the keys of a bound
+// hash right after `name[`.
+pub fn complete(env: &Environment, line: &str, pos: usize) -> (usize, Vec<String>) {
+    let before = &line[..pos];
+
+    // A hash key may be a string that is still open, so it goes first.
+    if let Some(completion) = complete_hash_key(env, before) {
+        return completion;
+    }
+
+    // Nothing else is completed inside a string.
+    let mut lexer = Lexer::new(before);
+    loop {
+        let token = lexer.next_token();
+
+        match token.typ {
+            TokenType::EOF => break,
+            TokenType::ILLEGAL if token.literal.starts_with('"') => return (pos, Vec::new()),
+            _ => {}
+        }
+    }
+
+    let start = word_start(before);
+    let word = &before[start..];
 
-use crate::{lexer::Lexer, token::TokenType};
+    if word.is_empty() || word.starts_with(|ch: char| ch.is_ascii_digit()) {
+        return (pos, Vec::new());
+    }
+
+    let mut candidates: Vec<String> = KEYWORDS
+        .iter()
+        .map(|keyword| keyword.to_string())
+        .chain(builtins::names())
+        .chain(env.bindings().into_iter().map(|(name, _)| name))
+        .filter(|candidate| candidate.starts_with(word))
+        .collect();
+    candidates.sort();
+    candidates.dedup();
+
+    (start, candidates)
+}
+
+// Completes a partly typed key in `name[`, when `name` is bound to a hash.
+fn complete_hash_key(env: &Environment, before: &str) -> Option<(usize, Vec<String>)> {
+    let open = before.rfind('[')?;
+    let key = &before[open + 1..];
+
+    let partial = match key.strip_prefix('"') {
+        Some(rest) => !rest.contains(['"', '\\']),
+        None => key.chars().all(|ch| ch.is_alphanumeric() || ch == '-'),
+    };
+    if !partial {
+        return None;
+    }
+
+    let target = &before[..open];
+    let Some(Object::Hash(pairs)) = env.get(&target[word_start(target)..]) else {
+        return None;
+    };
+
+    let candidates: Vec<String> = pairs
+        .keys()
+        .map(|key| key.inspect())
+        .filter(|candidate| candidate.starts_with(key))
+        .collect();
+
+    // `hash[le` may be the start of a name rather than a key.
+    if candidates.is_empty() {
+        return None;
+    }
+
+    Some((open + 1, candidates))
+}
+
+// Colors `line` token by token, as read by the lexer. Tokens it cannot
+// read are shown in red, and comments are dimmed.
+pub fn highlight(line: &str) -> String {
+    let mut lexer = Lexer::new(line);
+    let mut out = String::new();
+    let mut end = 0;
+    let mut comments = 0;
+
+    loop {
+        let token = lexer.next_token();
+        let (start, stop) = (token.span.start, token.span.end);
+
+        // Whatever the lexer skipped: whitespace and comments.
+        for comment in &lexer.comments()[comments..] {
+            let text = &line[comment.span.start..comment.span.end];
+
+            out.push_str(&line[end..comment.span.start]);
+            out.push_str(&text.dimmed().to_string());
+            end = comment.span.end;
+        }
+        comments = lexer.comments().len();
+        out.push_str(&line[end..start]);
+
+        if token.typ == TokenType::EOF {
+            return out;
+        }
+
+        let text = &line[start..stop];
+        let colored = match token.typ {
+            TokenType::LET
+            | TokenType::FUNCTION
+            | TokenType::IF
+            | TokenType::ELSE
+            | TokenType::RETURN => text.magenta().bold(),
+            TokenType::INT | TokenType::FLOAT | TokenType::TRUE | TokenType::FALSE => text.cyan(),
+            TokenType::STRING => text.green(),
+            TokenType::IDENT => text.blue(),
+            TokenType::ILLEGAL => text.red(),
+            TokenType::COMMA
+            | TokenType::SEMICOLON
+            | TokenType::COLON
+            | TokenType::LPAREN
+            | TokenType::RPAREN
+            | TokenType::LBRACE
+            | TokenType::RBRACE
+            | TokenType::LBRACKET
+            | TokenType::RBRACKET => text.normal(),
+            _ => text.yellow(),
+        };
+
+        out.push_str(&colored.to_string());
+        end = stop;
+    }
+}
+
+// Where the identifier that `text` ends with starts.
+fn word_start(text: &str) -> usize {
+    text.char_indices()
+        .rev()
+        .take_while(|(_, ch)| ch.is_alphanumeric() || *ch == '_')
+        .last()
+        .map_or(text.len(), |(i, _)| i)
+}
+
+// Plugs completion into the line editor. It holds a handle to the REPL's
+// environment, so it sees bindings as soon as they are made.
+struct ReplHelper {
+    env: Environment,
+}
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(complete(&self.env, line, pos))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {
+    fn highlight<'l>(&self, line: &'l str, _pos: usize) -> Cow<'l, str> {
+        if colored::control::SHOULD_COLORIZE.should_colorize() {
+            Cow::Owned(highlight(line))
+        } else {
+            Cow::Borrowed(line)
+        }
+    }
+
+    // Any edit can change how the rest of the line lexes.
+    fn highlight_char(&self, _line: &str, _pos: usize, kind: CmdKind) -> bool {
+        kind != CmdKind::MoveCursor
+    }
+}
+
+impl Validator for ReplHelper {}
+
+impl rustyline::Helper for ReplHelper {}
+
+// Whether `input` stops partway through, with brackets, a string or a
+// block comment left open, so more lines should be read before it runs.
+pub fn is_incomplete(input: &str) -> bool {
+    let mut lexer = Lexer::new(input);
+    let mut depth: i64 = 0;
+
+    loop {
+        let token = lexer.next_token();
+
+        match token.typ {
+            TokenType::LPAREN | TokenType::LBRACE | TokenType::LBRACKET => depth += 1,
+            TokenType::RPAREN | TokenType::RBRACE | TokenType::RBRACKET => depth -= 1,
+            // Unterminated strings and comments run to the end of input.
+            TokenType::ILLEGAL => {
+                let open = token.literal.starts_with('"') || token.literal == "/*";
+                if open && token.span.end == input.len() {
+                    return true;
+                }
+            }
+            TokenType::EOF => return depth > 0,
+            _ => {}
+        }
+    }
+}
+
+fn history_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("MONO_HISTORY") {
+        return Some(PathBuf::from(path));
+    }
+
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(HISTORY_FILE))
+}
 
 pub fn start() {
+    let mut editor: Editor<ReplHelper, DefaultHistory> = match Editor::new() {
+        Ok(editor) => editor,
+        Err(e) => {
+            eprintln!("could not start the REPL: {}", e);
+            return;
+        }
+    };
+
+    let history = history_path();
+    if let Some(path) = &history {
+        // There is no history on the first run.
+        let _ = editor.load_history(path);
+    }
+
+    let mut repl = Repl::new();
+    let mut input = String::new();
+
     loop {
-        print!("{} ", "mono >>".yellow());
-        let input: String = read!("{}\n");
+        // `:reset` replaces the environment, so the helper is given the
+        // current one each time.
+        editor.set_helper(Some(ReplHelper {
+            env: repl.environment().clone(),
+        }));
 
-        let mut lexer = Lexer::new(&input);
+        let prompt = if input.is_empty() {
+            PROMPT.yellow()
+        } else {
+            CONTINUATION_PROMPT.yellow()
+        };
 
-        loop {
-            let token = lexer.next_token();
+        match editor.readline(&prompt.to_string()) {
+            Ok(line) => {
+                input.push_str(&line);
+                input.push('\n');
 
-            if token.typ != TokenType::EOF {
-                println!("{:?}", token);
-            } else {
+                if is_incomplete(&input) {
+                    continue;
+                }
+
+                if !input.trim().is_empty() {
+                    let _ = editor.add_history_entry(input.trim_end());
+                    print!("{}", repl.eval(&input));
+                }
+                input.clear();
+            }
+            // Ctrl-C drops whatever has been typed so far.
+            Err(ReadlineError::Interrupted) => {
+                if input.is_empty() {
+                    println!("{}", "(press Ctrl-D to exit)".dimmed());
+                }
+                input.clear();
+            }
+            // Ctrl-D
+            Err(ReadlineError::Eof) => break,
+            Err(e) => {
+                eprintln!("{}", e);
                 break;
             }
         }
     }
+
+    if let Some(path) = &history {
+        if let Err(e) = editor.save_history(path) {
+            eprintln!("could not save history to {}: {}", path.display(), e);
+        }
+    }
 }
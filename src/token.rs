@@ -2,6 +2,7 @@
 pub struct Token {
     pub typ: TokenType,
     pub literal: String,
+    pub span: Span,
 }
 
 impl Token {
@@ -9,10 +10,47 @@ impl Token {
         Self {
             typ,
             literal: ch.to_string(),
+            span: Span::default(),
         }
     }
 }
 
+// A region of the source. `start` and `end` are byte offsets into the
+// input, while `line` and `column` (both starting at 1) locate `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+    // The span running from the start of `self` to the end of `other`.
+    pub fn to(self, other: Span) -> Span {
+        Span {
+            start: self.start,
+            end: other.end.max(self.end),
+            line: self.line,
+            column: self.column,
+        }
+    }
+}
+
+// A `//` or `/* */` comment, kept aside by the lexer so tools such as
+// the formatter can put it back.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    pub text: String,
+    pub span: Span,
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
 #[derive(PartialEq, Debug, Clone, Copy, Eq, Hash)]
 pub enum TokenType {
     ILLEGAL,
@@ -21,6 +59,8 @@ pub enum TokenType {
     // Identifiers and literals
     IDENT,
     INT,
+    FLOAT,
+    STRING,
 
     // Operators
     ASSIGN,
@@ -29,18 +69,28 @@ pub enum TokenType {
     BANG,
     ASTERISK,
     SLASH,
+    PERCENT,
+    POWER,
 
     LT,
     GT,
+    LTE,
+    GTE,
+
+    AND,
+    OR,
 
     // Delimiters
     COMMA,
     SEMICOLON,
+    COLON,
 
     LPAREN,
     RPAREN,
     LBRACE,
     RBRACE,
+    LBRACKET,
+    RBRACKET,
 
     // Keywords
     FUNCTION,
@@ -55,10 +105,13 @@ pub enum TokenType {
     NEQ,
 }
 
+// Every word `TokenType::serialize` reads as a keyword.
+pub const KEYWORDS: [&str; 7] = ["let", "fn", "true", "false", "if", "else", "return"];
+
 impl TokenType {
     // TODO: Refactor this using a hashmap.
-    pub fn serialize(string: &String) -> TokenType {
-        let value = match string.as_str() {
+    pub fn serialize(string: &str) -> TokenType {
+        match string {
             "let" => Self::LET,
             "fn" => Self::FUNCTION,
             "true" => Self::TRUE,
@@ -67,8 +120,6 @@ impl TokenType {
             "else" => Self::ELSE,
             "return" => Self::RETURN,
             _ => Self::IDENT,
-        };
-
-        value
+        }
     }
 }
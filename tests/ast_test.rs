@@ -1,8 +1,12 @@
 #[cfg(test)]
 mod tests {
     use mono::{
-        ast::{ExpressionVariants, Identifier, LetStatement, Node, Program, StatementVariant},
-        token::{Token, TokenType},
+        ast::{
+            tree, ExpressionVariants, Identifier, LetStatement, Node, Program, StatementVariant,
+        },
+        lexer::Lexer,
+        parser::Parser,
+        token::{Span, Token, TokenType},
     };
 
     #[test]
@@ -12,22 +16,30 @@ mod tests {
                 token: Token {
                     typ: TokenType::LET,
                     literal: "let".to_string(),
+                    span: Span::default(),
                 },
                 name: Identifier {
                     token: Token {
                         typ: TokenType::IDENT,
                         literal: "myVar".to_string(),
+                        span: Span::default(),
                     },
                     value: "myVar".to_string(),
+                    span: Span::default(),
                 },
                 value: Some(ExpressionVariants::Ident(Identifier {
                     token: Token {
                         typ: TokenType::IDENT,
                         literal: "anotherVar".to_string(),
+                        span: Span::default(),
                     },
                     value: "anotherVar".to_string(),
+                    span: Span::default(),
                 })),
+                doc: None,
+                span: Span::default(),
             })],
+            span: Span::default(),
         };
 
         if program.string() != "let myVar = anotherVar;" {
@@ -37,4 +49,70 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_round_trip() {
+        let tests: Vec<(&str, &str)> = vec![
+            ("let x = 5 * y;", "let x = (5 * y);"),
+            ("let f = fn(a) { a };", "let f = fn(a) { a };"),
+            ("return add(1, 2);", "return add(1, 2);"),
+            ("return;", "return;"),
+            ("fn() { return }", "fn() { return; }"),
+            ("if (a) { b } else { c }", "if (a) { b } else { c }"),
+            ("fn(x) { x; -1 }", "fn(x) { x; (-1) }"),
+            ("let f = fn() { g; [1] };", "let f = fn() { g; [1] };"),
+            ("a; b;", "a;b"),
+            ("let x = 1; x", "let x = 1;x"),
+        ];
+
+        for (input, expected) in tests {
+            let mut parser = Parser::new(Lexer::new(input));
+            let program = parser.parse_program();
+
+            if program.string() != expected {
+                panic!("Expected {}, got {}", expected, program.string());
+            }
+
+            // Printing the reparsed output must not lose anything either.
+            let mut reparser = Parser::new(Lexer::new(&program.string()));
+            if reparser.parse_program().string() != expected {
+                panic!("{} did not survive a second round trip", expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tree() {
+        let input = "let f = fn(x) { if (x > 1) { x } else { [x][0] } };\nf({\"a\": 2.5})";
+        let mut parser = Parser::new(Lexer::new(input));
+        let program = parser.parse_program();
+
+        let expected = "\
+Let f @ 1:1
+  Function(x) @ 1:9
+    Body @ 1:15
+      If @ 1:17
+        Infix > @ 1:21
+          Identifier x @ 1:21
+          Integer 1 @ 1:25
+        Then @ 1:28
+          Identifier x @ 1:30
+        Else @ 1:39
+          Index @ 1:41
+            Array @ 1:41
+              Identifier x @ 1:42
+            Integer 0 @ 1:45
+Call @ 2:1
+  Identifier f @ 2:1
+  Hash @ 2:3
+    Pair @ 2:4
+      String \"a\" @ 2:4
+      Float 2.5 @ 2:9
+";
+
+        let tree = tree(&program);
+        if tree != expected {
+            panic!("Expected\n{}\ngot\n{}", expected, tree);
+        }
+    }
 }
@@ -0,0 +1,138 @@
+#![cfg(feature = "bigint")]
+
+#[cfg(test)]
+mod tests {
+    use mono::{
+        environment::Environment, evaluator::eval, lexer::Lexer, object::Object, parser::Parser,
+    };
+
+    fn test_inspect(input: &str, expected: &str) {
+        let mut parser = Parser::new(Lexer::new(input));
+        let program = parser.parse_program();
+
+        if !parser.errors().is_empty() {
+            panic!("{} has parser errors: {:?}", input, parser.errors());
+        }
+
+        let evaluated = eval(&program, &mut Environment::new());
+        if evaluated.inspect() != expected {
+            panic!(
+                "{} should evaluate to {}, got {}",
+                input,
+                expected,
+                evaluated.inspect()
+            );
+        }
+    }
+
+    #[test]
+    fn test_promotion_on_overflow() {
+        let tests: Vec<(&str, &str)> = vec![
+            ("9223372036854775807 + 1", "9223372036854775808"),
+            ("-9223372036854775807 - 2", "-9223372036854775809"),
+            ("4611686018427387904 * 2", "9223372036854775808"),
+            ("(-9223372036854775807 - 1) / -1", "9223372036854775808"),
+            ("-(-9223372036854775807 - 1)", "9223372036854775808"),
+            ("2 ** 100", "1267650600228229401496703205376"),
+            ("type(2 ** 64)", r#""INTEGER""#),
+        ];
+
+        for (input, expected) in tests {
+            test_inspect(input, expected);
+        }
+    }
+
+    #[test]
+    fn test_demotion_to_i64() {
+        let evaluated = {
+            let mut parser = Parser::new(Lexer::new("2 ** 64 - 2 ** 64 + 1"));
+            eval(&parser.parse_program(), &mut Environment::new())
+        };
+
+        if !matches!(evaluated, Object::Integer(1)) {
+            panic!("Expected Integer(1), got {:?}", evaluated);
+        }
+
+        test_inspect("-9223372036854775808", "-9223372036854775808");
+        test_inspect("[1, 2, 3][2 ** 64 - 2 ** 64]", "1");
+    }
+
+    #[test]
+    fn test_big_integer_literals() {
+        let tests: Vec<(&str, &str)> = vec![
+            ("99999999999999999999", "99999999999999999999"),
+            ("99_999_999_999_999_999_999 + 1", "100000000000000000000"),
+            ("0xFFFF_FFFF_FFFF_FFFF", "18446744073709551615"),
+            ("0b1", "1"),
+            ("100000000000000000000 / 3", "33333333333333333333"),
+            ("100000000000000000000 % 7", "2"),
+            ("100000000000000000000 > 9223372036854775807", "true"),
+            ("100000000000000000000 == 10 ** 20", "true"),
+            ("100000000000000000000 * 0.5", "5e19"),
+            (
+                r#"int("123456789012345678901234567890")"#,
+                "123456789012345678901234567890",
+            ),
+            (r#"{10 ** 20: "big"}[100000000000000000000]"#, r#""big""#),
+        ];
+
+        for (input, expected) in tests {
+            test_inspect(input, expected);
+        }
+    }
+
+    #[test]
+    fn test_factorial() {
+        test_inspect(
+            "let fact = fn(n) { if (n < 2) { 1 } else { n * fact(n - 1) } }; fact(30)",
+            "265252859812191058636308480000000",
+        );
+    }
+
+    #[test]
+    fn test_powers_of_small_bases() {
+        // Powers of 0, 1 and -1 stay small, so any exponent works.
+        test_inspect("1 ** 4000000000", "1");
+        test_inspect("(-1) ** 4000000001", "-1");
+        test_inspect("0 ** 4000000000", "0");
+        test_inspect("1 ** (2 ** 70)", "1");
+        test_inspect("(-1) ** (2 ** 70)", "1");
+        test_inspect("(-1) ** (2 ** 70 + 1)", "-1");
+        test_inspect("0 ** (2 ** 70)", "0");
+        test_inspect("0 ** (2 ** 70 - 2 ** 70)", "1");
+    }
+
+    #[test]
+    fn test_big_integer_errors() {
+        let tests: Vec<(&str, &str)> = vec![
+            (
+                "100000000000000000000 / 0",
+                "ERROR: 1:1: division by zero: 100000000000000000000 / 0",
+            ),
+            (
+                "2 ** -(2 ** 70)",
+                "ERROR: 1:1: negative exponent: 2 ** -1180591620717411303424",
+            ),
+            (
+                "2 ** (2 ** 70)",
+                "ERROR: 1:1: exponent too large: 2 ** 1180591620717411303424",
+            ),
+            (
+                "3 ** 4000000000",
+                "ERROR: 1:1: exponent too large: 3 ** 4000000000",
+            ),
+            (
+                "[1, 2][2 ** 70]",
+                "ERROR: 1:1: index out of range: 1180591620717411303424 (length 2)",
+            ),
+            (
+                "10 ** 20 + true",
+                "ERROR: 1:1: type mismatch: INTEGER + BOOLEAN",
+            ),
+        ];
+
+        for (input, expected) in tests {
+            test_inspect(input, expected);
+        }
+    }
+}
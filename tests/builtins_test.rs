@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use mono::{
+        builtins::{lookup, register_builtin},
+        environment::Environment,
+        evaluator::eval,
+        lexer::Lexer,
+        object::{Arity, Object},
+        parser::Parser,
+    };
+
+    fn test_eval(input: &str) -> Object {
+        let mut parser = Parser::new(Lexer::new(input));
+        let program = parser.parse_program();
+
+        eval(&program, &mut Environment::new())
+    }
+
+    fn test_inspect(input: &str, expected: &str) {
+        let evaluated = test_eval(input);
+
+        if evaluated.inspect() != expected {
+            panic!(
+                "{} evaluated to {}, expected {}",
+                input,
+                evaluated.inspect(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_conversion_builtins() {
+        let tests: Vec<(&str, &str)> = vec![
+            ("type(1)", r#""INTEGER""#),
+            (r#"type("a")"#, r#""STRING""#),
+            ("type([])", r#""ARRAY""#),
+            ("type(len)", r#""BUILTIN""#),
+            ("str(42)", r#""42""#),
+            (r#"str("already")"#, r#""already""#),
+            (r#"str([1, "a"])"#, r#""[1, \"a\"]""#),
+            (r#"int("42")"#, "42"),
+            (r#"int(" -7 ")"#, "-7"),
+            ("int(true)", "1"),
+            ("int(5)", "5"),
+            ("int(-3.7)", "-3"),
+            ("float(2)", "2.0"),
+            (r#"float(" 0.25 ")"#, "0.25"),
+            ("float(1.5)", "1.5"),
+            ("type(1.5)", r#""FLOAT""#),
+            ("str(1e-9)", r#""1e-9""#),
+            ("1e21 * 10", "1e22"),
+            (
+                r#"float("half")"#,
+                r#"ERROR: 1:1: could not convert "half" to FLOAT"#,
+            ),
+            ("int(1e30)", "ERROR: 1:1: could not convert 1e30 to INTEGER"),
+            (
+                "int(9223372036854775807.0)",
+                "ERROR: 1:1: could not convert 9.223372036854776e18 to INTEGER",
+            ),
+            ("int(-9223372036854775808.0)", "-9223372036854775808"),
+            ("puts(1, 2)", "null"),
+            (
+                r#"int("forty")"#,
+                r#"ERROR: 1:1: could not convert "forty" to INTEGER"#,
+            ),
+            (
+                "int([])",
+                "ERROR: 1:1: argument to `int` not supported, got ARRAY",
+            ),
+            (
+                "type(1, 2)",
+                "ERROR: 1:1: wrong number of arguments: want=1, got=2",
+            ),
+        ];
+
+        for (input, expected) in tests {
+            test_inspect(input, expected);
+        }
+    }
+
+    #[test]
+    fn test_register_builtin() {
+        register_builtin("double", Arity::Exact(1), |arguments| match &arguments[0] {
+            Object::Integer(value) => Ok(Object::Integer(value * 2)),
+            other => Err(format!("cannot double {}", other.type_name())),
+        });
+
+        if lookup("double").is_none() {
+            panic!("double was not registered");
+        }
+
+        test_inspect("double(21)", "42");
+        test_inspect("double(true)", "ERROR: 1:1: cannot double BOOLEAN");
+        test_inspect(
+            "double()",
+            "ERROR: 1:1: wrong number of arguments: want=1, got=0",
+        );
+        // Bindings in the environment shadow registered builtins.
+        test_inspect("let double = fn(x) { x }; double(21)", "21");
+    }
+
+    #[test]
+    fn test_unknown_identifier() {
+        if lookup("not_a_builtin").is_some() {
+            panic!("lookup found a builtin that was never registered");
+        }
+
+        test_inspect(
+            "not_a_builtin(1)",
+            "ERROR: 1:1: identifier not found: not_a_builtin",
+        );
+    }
+}
@@ -0,0 +1,175 @@
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf, process::Command};
+
+    struct Output {
+        status: i32,
+        stdout: String,
+        stderr: String,
+    }
+
+    fn mono(args: &[&str]) -> Output {
+        let output = Command::new(env!("CARGO_BIN_EXE_mono"))
+            .args(args)
+            .env("NO_COLOR", "1")
+            .output()
+            .expect("failed to run mono");
+
+        Output {
+            status: output.status.code().unwrap_or(-1),
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        }
+    }
+
+    // Writes a script to a file of its own in the temp directory.
+    fn script(name: &str, source: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("mono-cli-{}-{}.mono", std::process::id(), name));
+        fs::write(&path, source).expect("failed to write script");
+
+        path
+    }
+
+    #[test]
+    fn test_run() {
+        let path = script(
+            "run",
+            "let greet = fn(name) { \"hola \" + name };\nputs(greet(\"mundo\"));\n",
+        );
+        let file = path.to_str().unwrap();
+
+        for args in [vec!["run", file], vec![file]] {
+            let output = mono(&args);
+
+            if output.status != 0 || output.stdout != "hola mundo\n" || !output.stderr.is_empty() {
+                panic!(
+                    "Unexpected result for {:?}: {} {:?} {:?}",
+                    args, output.status, output.stdout, output.stderr
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_eval_expression() {
+        let tests: Vec<(&str, &str)> = vec![
+            ("1 + 2 * 3", "7\n"),
+            ("let a = [1, 2]; push(a, 3)", "[1, 2, 3]\n"),
+            ("\"texto\"", "\"texto\"\n"),
+            ("puts(\"hi\")", "hi\n"),
+            ("let x = 1;", ""),
+        ];
+
+        for (source, expected) in tests {
+            let output = mono(&["-e", source]);
+
+            if output.status != 0 || output.stdout != expected {
+                panic!(
+                    "Expected {:?} for {:?}, got {} {:?} {:?}",
+                    expected, source, output.status, output.stdout, output.stderr
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        let tests: Vec<(Vec<&str>, i32, &str)> = vec![
+            (
+                vec!["-e", "let x 5; let = 1;"],
+                65,
+                "error[E0001]: Expected next token to be ASSIGN, got INT instead\n --> <expr>:1:7\n",
+            ),
+            (
+                vec!["-e", "puts(1);\n1 / 0"],
+                70,
+                "error[E0100]: division by zero: 1 / 0\n --> <expr>:2:1\n  |\n2 | 1 / 0\n  | ^^^^^\n",
+            ),
+            // Deep recursion is an error, not a crash.
+            (
+                vec!["-e", "let f = fn(n) { f(n + 1) }; f(0)"],
+                70,
+                "error[E0100]: maximum call depth exceeded",
+            ),
+            (vec!["run", "/nonexistent/script.mono"], 66, "/nonexistent/script.mono: "),
+            (vec!["run"], 64, "usage: mono"),
+            (vec!["--bogus"], 64, "usage: mono"),
+        ];
+
+        for (args, status, stderr) in tests {
+            let output = mono(&args);
+
+            if output.status != status || !output.stderr.starts_with(stderr) {
+                panic!(
+                    "Expected exit {} with {:?} for {:?}, got {} {:?}",
+                    status, stderr, args, output.status, output.stderr
+                );
+            }
+        }
+
+        // Every parse error is reported, not just the first.
+        let output = mono(&["-e", "let x 5; let = 1;"]);
+        if output.stderr.matches("error[E0001]").count() != 2 {
+            panic!("Expected two parse errors, got {:?}", output.stderr);
+        }
+    }
+
+    #[test]
+    fn test_tokens_and_ast() {
+        let path = script("inspect", "let x = -1;");
+        let file = path.to_str().unwrap();
+
+        let output = mono(&["tokens", file]);
+        let expected = "1:1 LET \"let\"\n1:5 IDENT \"x\"\n1:7 ASSIGN \"=\"\n1:9 MINUS \"-\"\n1:10 INT \"1\"\n1:11 SEMICOLON \";\"\n1:12 EOF \" \"\n";
+        if output.status != 0 || output.stdout != expected {
+            panic!("Expected tokens {:?}, got {:?}", expected, output.stdout);
+        }
+
+        let output = mono(&["ast", file]);
+        let expected = "Let x @ 1:1\n  Prefix - @ 1:9\n    Integer 1 @ 1:10\n";
+        if output.status != 0 || output.stdout != expected {
+            panic!("Expected tree {:?}, got {:?}", expected, output.stdout);
+        }
+    }
+
+    #[test]
+    fn test_fmt() {
+        let path = script("fmt", "let   x=1");
+        let file = path.to_str().unwrap();
+
+        let output = mono(&["fmt", "--check", file]);
+        if output.status != 1 || !output.stdout.contains("is not formatted") {
+            panic!(
+                "Expected --check to fail, got {} {:?}",
+                output.status, output.stdout
+            );
+        }
+
+        let output = mono(&["fmt", file]);
+        let formatted = fs::read_to_string(&path).unwrap_or_default();
+        if output.status != 0 || formatted != "let x = 1;\n" {
+            panic!("Expected the file to be formatted, got {:?}", formatted);
+        }
+
+        let output = mono(&["fmt", "--check", file]);
+        if output.status != 0 {
+            panic!(
+                "Expected --check to pass, got {} {:?}",
+                output.status, output.stdout
+            );
+        }
+
+        // The most serious failure wins, whatever order the files are in.
+        let broken = script("fmt-broken", "let = 1;");
+        let broken = broken.to_str().unwrap();
+        let missing = "/nonexistent/script.mono";
+
+        for args in [["fmt", broken, missing], ["fmt", missing, broken]] {
+            let output = mono(&args);
+
+            if output.status != 66 {
+                panic!("Expected exit 66 for {:?}, got {}", args, output.status);
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod tests {
+    use mono::{
+        diagnostic::{Diagnostic, Severity},
+        lexer::Lexer,
+        parser::Parser,
+        token::Span,
+    };
+
+    fn parse_errors(input: &str) -> Vec<Diagnostic> {
+        let mut parser = Parser::new(Lexer::new(input));
+        parser.parse_program();
+
+        parser.errors().to_vec()
+    }
+
+    fn render(diagnostic: &Diagnostic, source: &str) -> String {
+        colored::control::set_override(false);
+
+        diagnostic.render("test.mono", source)
+    }
+
+    #[test]
+    fn test_parser_diagnostics() {
+        let tests: Vec<(&str, &str, &str)> = vec![
+            (
+                "let x 5;",
+                "E0001",
+                "Expected next token to be ASSIGN, got INT instead",
+            ),
+            (")", "E0002", "No prefix parse function for RPAREN found"),
+            ("@", "E0003", "Illegal token @"),
+            (
+                "0b102",
+                "E0004",
+                "Could not parse 0b102 as an integer: invalid digit found in string",
+            ),
+        ];
+
+        for (input, code, message) in tests {
+            let errors = parse_errors(input);
+
+            match errors.first() {
+                Some(diagnostic) => {
+                    if diagnostic.code != code || diagnostic.message != message {
+                        panic!(
+                            "Expected {} {:?}, got {} {:?}",
+                            code, message, diagnostic.code, diagnostic.message
+                        );
+                    }
+
+                    if diagnostic.severity != Severity::Error {
+                        panic!("Expected an error, got {:?}", diagnostic.severity);
+                    }
+                }
+                None => panic!("{} produced no diagnostics", input),
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_snippet() {
+        let source = "let a = 1;\nlet x 5;\n";
+        let errors = parse_errors(source);
+
+        let expected = "\
+error[E0001]: Expected next token to be ASSIGN, got INT instead
+ --> test.mono:2:7
+  |
+2 | let x 5;
+  |       ^ expected ASSIGN here
+";
+
+        let rendered = render(&errors[0], source);
+        if rendered != expected {
+            panic!("Expected\n{}\ngot\n{}", expected, rendered);
+        }
+    }
+
+    #[test]
+    fn test_render_labels_notes_and_help() {
+        let source = "let name = \"mono\";\n\nlen(name, name)\n";
+        let span = |start: usize, end: usize, line: usize, column: usize| Span {
+            start,
+            end,
+            line,
+            column,
+        };
+
+        let diagnostic = Diagnostic::error(
+            "E9999",
+            "wrong number of arguments".to_string(),
+            span(20, 35, 3, 1),
+        )
+        .with_label("called with 2 arguments")
+        .with_secondary(span(4, 8, 1, 5), "defined here")
+        .with_note("len takes exactly one argument")
+        .with_help("remove the second argument");
+
+        let expected = "\
+error[E9999]: wrong number of arguments
+ --> test.mono:3:1
+  |
+1 | let name = \"mono\";
+  |     ---- defined here
+3 | len(name, name)
+  | ^^^^^^^^^^^^^^^ called with 2 arguments
+  = note: len takes exactly one argument
+  = help: remove the second argument
+";
+
+        let rendered = render(&diagnostic, source);
+        if rendered != expected {
+            panic!("Expected\n{}\ngot\n{}", expected, rendered);
+        }
+    }
+
+    #[test]
+    fn test_render_multiline_span_and_eof() {
+        // Spans that run over several lines only underline the first one.
+        let source = "if (x) {\n  1\n";
+        let diagnostic = Diagnostic::error(
+            "E9998",
+            "unclosed block".to_string(),
+            Span {
+                start: 7,
+                end: 13,
+                line: 1,
+                column: 8,
+            },
+        );
+
+        let rendered = render(&diagnostic, source);
+        if !rendered.contains("1 | if (x) {\n  |        ^\n") {
+            panic!("Unexpected rendering\n{}", rendered);
+        }
+
+        let eof = parse_errors("let x =");
+        let rendered = render(&eof[0], "let x =");
+        if !rendered.contains("1 | let x =\n  |        ^") {
+            panic!("Unexpected rendering at end of input\n{}", rendered);
+        }
+    }
+}
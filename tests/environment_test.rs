@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use mono::{environment::Environment, object::Object};
+
+    fn test_integer_binding(env: &Environment, name: &str, expected: i64) {
+        match env.get(name) {
+            Some(Object::Integer(value)) => {
+                if value != expected {
+                    panic!(
+                        "{} has wrong value, expected {}, got {}",
+                        name, expected, value
+                    );
+                }
+            }
+            other => panic!("{} is not bound to an Integer, got {:?}", name, other),
+        }
+    }
+
+    #[test]
+    fn test_enclosed_lookup() {
+        let mut outer = Environment::new();
+        outer.set("x", Object::Integer(1));
+        outer.set("y", Object::Integer(2));
+
+        let mut inner = Environment::new_enclosed(&outer);
+        inner.set("y", Object::Integer(20));
+
+        test_integer_binding(&inner, "x", 1);
+        test_integer_binding(&inner, "y", 20);
+        // Shadowing in the inner scope leaves the outer binding untouched.
+        test_integer_binding(&outer, "y", 2);
+
+        if inner.get("z").is_some() {
+            panic!("z should not be bound");
+        }
+    }
+
+    #[test]
+    fn test_captured_scope_is_shared() {
+        let mut outer = Environment::new();
+        let captured = outer.clone();
+        let inner = Environment::new_enclosed(&captured);
+
+        // Bindings made after the capture are still visible through it.
+        outer.set("later", Object::Integer(42));
+
+        test_integer_binding(&captured, "later", 42);
+        test_integer_binding(&inner, "later", 42);
+    }
+
+    #[test]
+    fn test_bindings() {
+        let mut outer = Environment::new();
+        outer.set("b", Object::Integer(1));
+        outer.set("a", Object::Integer(2));
+
+        let mut inner = Environment::new_enclosed(&outer);
+        inner.set("b", Object::Integer(3));
+        inner.set("c", Object::Integer(4));
+
+        let bindings: Vec<String> = inner
+            .bindings()
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value.inspect()))
+            .collect();
+        let expected = vec!["a=2", "b=3", "c=4"];
+
+        if bindings != expected {
+            panic!("Expected {:?}, got {:?}", expected, bindings);
+        }
+    }
+}
@@ -0,0 +1,777 @@
+#[cfg(test)]
+mod tests {
+    use mono::{
+        environment::Environment,
+        evaluator::{eval, MAX_CALL_DEPTH},
+        lexer::Lexer,
+        object::Object,
+        parser::Parser,
+    };
+
+    fn test_eval(input: &str) -> Object {
+        let lexer = Lexer::new(input);
+        let mut parser = Parser::new(lexer);
+        let program = parser.parse_program();
+        let mut env = Environment::new();
+
+        eval(&program, &mut env)
+    }
+
+    fn test_integer_object(object: &Object, expected: i64) {
+        if let Object::Integer(value) = object {
+            if *value != expected {
+                panic!(
+                    "object has wrong value, expected {}, got {}",
+                    expected, value
+                );
+            }
+        } else {
+            panic!("object is not Integer, got {:?}", object);
+        }
+    }
+
+    fn test_float_object(object: &Object, expected: f64) {
+        if let Object::Float(value) = object {
+            if (*value - expected).abs() > 1e-12 {
+                panic!(
+                    "object has wrong value, expected {}, got {}",
+                    expected, value
+                );
+            }
+        } else {
+            panic!("object is not Float, got {:?}", object);
+        }
+    }
+
+    fn test_boolean_object(object: &Object, expected: bool) {
+        if let Object::Boolean(value) = object {
+            if *value != expected {
+                panic!(
+                    "object has wrong value, expected {}, got {}",
+                    expected, value
+                );
+            }
+        } else {
+            panic!("object is not Boolean, got {:?}", object);
+        }
+    }
+
+    fn test_null_object(object: &Object) {
+        if !matches!(object, Object::Null) {
+            panic!("object is not Null, got {:?}", object);
+        }
+    }
+
+    #[test]
+    fn test_eval_integer_expression() {
+        let tests: Vec<(&str, i64)> = vec![
+            ("5", 5),
+            ("10", 10),
+            ("-5", -5),
+            ("-10", -10),
+            ("5 + 5 + 5 + 5 - 10", 10),
+            ("2 * 2 * 2 * 2 * 2", 32),
+            ("-50 + 100 + -50", 0),
+            ("5 * 2 + 10", 20),
+            ("5 + 2 * 10", 25),
+            ("20 + 2 * -10", 0),
+            ("50 / 2 * 2 + 10", 60),
+            ("2 * (5 + 10)", 30),
+            ("3 * 3 * 3 + 10", 37),
+            ("3 * (3 * 3) + 10", 37),
+            ("(5 + 10 * 2 + 15 / 3) * 2 + -10", 50),
+            ("10 % 3", 1),
+            ("-7 % 3", -1),
+            ("2 + 10 % 4 * 3", 8),
+            ("2 ** 10", 1024),
+            ("2 ** 3 ** 2", 512),
+            ("-2 ** 2", -4),
+            ("(-2) ** 3", -8),
+            ("7 ** 0", 1),
+            ("0 ** 0", 1),
+            ("1 ** 5000000000", 1),
+            ("0 ** 5000000000", 0),
+            ("(-1) ** 5000000000", 1),
+            ("(-1) ** 5000000001", -1),
+        ];
+
+        for (input, expected) in tests {
+            test_integer_object(&test_eval(input), expected);
+        }
+    }
+
+    #[test]
+    fn test_eval_boolean_expression() {
+        let tests: Vec<(&str, bool)> = vec![
+            ("true", true),
+            ("false", false),
+            ("1 < 2", true),
+            ("1 > 2", false),
+            ("1 < 1", false),
+            ("1 == 1", true),
+            ("1 != 1", false),
+            ("1 == 2", false),
+            ("1 != 2", true),
+            ("true == true", true),
+            ("false == false", true),
+            ("true == false", false),
+            ("true != false", true),
+            ("(1 < 2) == true", true),
+            ("(1 > 2) == true", false),
+            ("1 <= 2", true),
+            ("2 <= 2", true),
+            ("3 <= 2", false),
+            ("1 >= 2", false),
+            ("2 >= 2", true),
+            ("true && true", true),
+            ("true && false", false),
+            ("false || true", true),
+            ("false || false", false),
+            ("1 < 2 && 2 < 3", true),
+            ("1 && \"\"", true),
+            ("false || 0 == 1", false),
+        ];
+
+        for (input, expected) in tests {
+            test_boolean_object(&test_eval(input), expected);
+        }
+    }
+
+    #[test]
+    fn test_bang_operator() {
+        let tests: Vec<(&str, bool)> = vec![
+            ("!true", false),
+            ("!false", true),
+            ("!5", false),
+            ("!!true", true),
+            ("!!false", false),
+            ("!!5", true),
+        ];
+
+        for (input, expected) in tests {
+            test_boolean_object(&test_eval(input), expected);
+        }
+    }
+
+    #[test]
+    fn test_if_expressions() {
+        let tests: Vec<(&str, Option<i64>)> = vec![
+            ("if (true) { 10 }", Some(10)),
+            ("if (false) { 10 }", None),
+            ("if (1) { 10 }", Some(10)),
+            ("if (1 < 2) { 10 }", Some(10)),
+            ("if (1 > 2) { 10 }", None),
+            ("if (1 > 2) { 10 } else { 20 }", Some(20)),
+            ("if (1 < 2) { 10 } else { 20 }", Some(10)),
+            (
+                "if (1 > 2) { 10 } else if (2 > 1) { 30 } else { 20 }",
+                Some(30),
+            ),
+            ("if (1 > 2) { 10 } else if (2 > 3) { 30 }", None),
+        ];
+
+        for (input, expected) in tests {
+            let evaluated = test_eval(input);
+
+            match expected {
+                Some(value) => test_integer_object(&evaluated, value),
+                None => test_null_object(&evaluated),
+            }
+        }
+    }
+
+    #[test]
+    fn test_let_statements() {
+        let tests: Vec<(&str, i64)> = vec![
+            ("let a = 5; a;", 5),
+            ("let a = 5 * 5; a;", 25),
+            ("let a = 5; let b = a; b;", 5),
+            ("let a = 5; let b = a; let c = a + b + 5; c;", 15),
+        ];
+
+        for (input, expected) in tests {
+            test_integer_object(&test_eval(input), expected);
+        }
+    }
+
+    #[test]
+    fn test_error_handling() {
+        let tests: Vec<(&str, &str)> = vec![
+            ("5 + true;", "type mismatch: INTEGER + BOOLEAN"),
+            ("5 + true; 5;", "type mismatch: INTEGER + BOOLEAN"),
+            ("-true", "unknown operator: -BOOLEAN"),
+            ("true + false;", "unknown operator: BOOLEAN + BOOLEAN"),
+            ("5; true + false; 5", "unknown operator: BOOLEAN + BOOLEAN"),
+            (
+                "if (10 > 1) { true + false; }",
+                "unknown operator: BOOLEAN + BOOLEAN",
+            ),
+            (
+                "if (10 > 1) { if (10 > 1) { true + false; } 1; }",
+                "unknown operator: BOOLEAN + BOOLEAN",
+            ),
+            ("foobar", "identifier not found: foobar"),
+            (r#""Hello" - "World""#, "unknown operator: STRING - STRING"),
+            (r#""Hello" + 1"#, "type mismatch: STRING + INTEGER"),
+        ];
+
+        for (input, expected) in tests {
+            let evaluated = test_eval(input);
+
+            if let Object::Error(error) = &evaluated {
+                if error.message != expected {
+                    panic!(
+                        "wrong error message, expected {}, got {}",
+                        expected, error.message
+                    );
+                }
+            } else {
+                panic!("no error object returned, got {:?}", evaluated);
+            }
+        }
+    }
+
+    #[test]
+    fn test_function_object() {
+        let evaluated = test_eval("fn(x) { x + 2; };");
+
+        if let Object::Function(function) = &evaluated {
+            if function.parameters.len() != 1 || function.parameters[0].value != "x" {
+                panic!(
+                    "function has wrong parameters, got {:?}",
+                    function.parameters
+                );
+            }
+
+            if function.inspect() != "fn(x) { (x + 2) }" {
+                panic!("function inspect is wrong, got {}", function.inspect());
+            }
+        } else {
+            panic!("object is not Function, got {:?}", evaluated);
+        }
+    }
+
+    #[test]
+    fn test_function_application() {
+        let tests: Vec<(&str, i64)> = vec![
+            ("let identity = fn(x) { x; }; identity(5);", 5),
+            ("let double = fn(x) { x * 2; }; double(5);", 10),
+            ("let add = fn(x, y) { x + y; }; add(5, 5);", 10),
+            ("let add = fn(x, y) { x + y; }; add(5 + 5, add(5, 5));", 20),
+            ("fn(x) { x; }(5)", 5),
+        ];
+
+        for (input, expected) in tests {
+            test_integer_object(&test_eval(input), expected);
+        }
+    }
+
+    #[test]
+    fn test_closures() {
+        let input = "
+            let new_adder = fn(x) {
+                fn(y) { x + y };
+            };
+
+            let add_two = new_adder(2);
+            add_two(2);";
+
+        test_integer_object(&test_eval(input), 4);
+    }
+
+    #[test]
+    fn test_function_errors() {
+        let tests: Vec<(&str, &str)> = vec![
+            (
+                "let add = fn(x, y) { x + y; }; add(1);",
+                "wrong number of arguments: want=2, got=1",
+            ),
+            ("let x = 5; x(1);", "not a function: INTEGER"),
+            ("let f = fn(x) { x; }; f(y);", "identifier not found: y"),
+        ];
+
+        for (input, expected) in tests {
+            let evaluated = test_eval(input);
+
+            if let Object::Error(error) = &evaluated {
+                if error.message != expected {
+                    panic!(
+                        "wrong error message, expected {}, got {}",
+                        expected, error.message
+                    );
+                }
+            } else {
+                panic!("no error object returned, got {:?}", evaluated);
+            }
+        }
+    }
+
+    #[test]
+    fn test_call_depth() {
+        let countdown = "let f = fn(n) { if (n == 0) { 0 } else { f(n - 1) } };";
+
+        // Test threads have small stacks, so this runs on one as large
+        // as the `mono` binary uses.
+        let result = std::thread::Builder::new()
+            .stack_size(MAX_CALL_DEPTH * 64 * 1024)
+            .spawn(move || {
+                let deepest = format!("{} f({})", countdown, MAX_CALL_DEPTH - 1);
+                test_integer_object(&test_eval(&deepest), 0);
+
+                match test_eval(&format!("{} f(100000)", countdown)) {
+                    Object::Error(error)
+                        if error.message.starts_with("maximum call depth exceeded") => {}
+                    other => panic!("Expected the call depth error, got {:?}", other),
+                }
+
+                // The depth is unwound along with the error.
+                test_integer_object(&test_eval(&deepest), 0);
+            })
+            .unwrap()
+            .join();
+
+        if let Err(panic) = result {
+            std::panic::resume_unwind(panic);
+        }
+    }
+
+    #[test]
+    fn test_return_statements() {
+        let tests: Vec<(&str, i64)> = vec![
+            ("return 10;", 10),
+            ("return 10; 9;", 10),
+            ("return 2 * 5; 9;", 10),
+            ("9; return 2 * 5; 9;", 10),
+            (
+                "if (10 > 1) {
+                    if (10 > 1) {
+                        return 10;
+                    }
+
+                    return 1;
+                }",
+                10,
+            ),
+            (
+                "let f = fn(x) {
+                    if (x > 1) { return x; }
+                    return 0;
+                };
+                f(5) + f(0);",
+                5,
+            ),
+        ];
+
+        for (input, expected) in tests {
+            test_integer_object(&test_eval(input), expected);
+        }
+
+        test_null_object(&test_eval("return; 5;"));
+        test_null_object(&test_eval("fn() { return }()"));
+    }
+
+    fn test_string_object(object: &Object, expected: &str) {
+        if let Object::String(value) = object {
+            if value != expected {
+                panic!(
+                    "object has wrong value, expected {:?}, got {:?}",
+                    expected, value
+                );
+            }
+        } else {
+            panic!("object is not String, got {:?}", object);
+        }
+    }
+
+    #[test]
+    fn test_string_literal() {
+        test_string_object(&test_eval(r#""Hello World!""#), "Hello World!");
+    }
+
+    #[test]
+    fn test_string_concatenation() {
+        test_string_object(&test_eval(r#""Hello" + " " + "World!""#), "Hello World!");
+
+        test_string_object(
+            &test_eval(r#"let greet = fn(name) { "Hi, " + name }; greet("Mono")"#),
+            "Hi, Mono",
+        );
+    }
+
+    #[test]
+    fn test_string_comparison() {
+        let tests: Vec<(&str, bool)> = vec![
+            (r#""a" == "a""#, true),
+            (r#""a" == "b""#, false),
+            (r#""a" != "b""#, true),
+            (r#""a" != "a""#, false),
+        ];
+
+        for (input, expected) in tests {
+            test_boolean_object(&test_eval(input), expected);
+        }
+    }
+
+    fn test_error_object(object: &Object, expected: &str) {
+        if let Object::Error(error) = object {
+            if error.message != expected {
+                panic!(
+                    "wrong error message, expected {}, got {}",
+                    expected, error.message
+                );
+            }
+        } else {
+            panic!("no error object returned, got {:?}", object);
+        }
+    }
+
+    #[test]
+    fn test_array_literals() {
+        let evaluated = test_eval("[1, 2 * 2, 3 + 3]");
+
+        if let Object::Array(elements) = &evaluated {
+            if elements.len() != 3 {
+                panic!("array has wrong number of elements, got {}", elements.len());
+            }
+
+            test_integer_object(&elements[0], 1);
+            test_integer_object(&elements[1], 4);
+            test_integer_object(&elements[2], 6);
+        } else {
+            panic!("object is not Array, got {:?}", evaluated);
+        }
+    }
+
+    #[test]
+    fn test_array_index_expressions() {
+        let tests: Vec<(&str, i64)> = vec![
+            ("[1, 2, 3][0]", 1),
+            ("[1, 2, 3][1]", 2),
+            ("[1, 2, 3][2]", 3),
+            ("let i = 0; [1][i];", 1),
+            ("[1, 2, 3][1 + 1];", 3),
+            ("let myArray = [1, 2, 3]; myArray[2];", 3),
+            (
+                "let myArray = [1, 2, 3]; myArray[0] + myArray[1] + myArray[2];",
+                6,
+            ),
+            ("let myArray = [1, 2, 3]; let i = myArray[0]; myArray[i]", 2),
+        ];
+
+        for (input, expected) in tests {
+            test_integer_object(&test_eval(input), expected);
+        }
+
+        let errors: Vec<(&str, &str)> = vec![
+            ("[1, 2, 3][3]", "index out of range: 3 (length 3)"),
+            ("[1, 2, 3][-1]", "index out of range: -1 (length 3)"),
+            ("[][0]", "index out of range: 0 (length 0)"),
+            ("1[0]", "index operator not supported: INTEGER[INTEGER]"),
+        ];
+
+        for (input, expected) in errors {
+            test_error_object(&test_eval(input), expected);
+        }
+    }
+
+    #[test]
+    fn test_builtin_functions() {
+        let tests: Vec<(&str, i64)> = vec![
+            (r#"len("")"#, 0),
+            (r#"len("four")"#, 4),
+            (r#"len("hello world")"#, 11),
+            ("len([1, 2, 3])", 3),
+            ("len([])", 0),
+            (r#"len({"a": 1, "b": 2})"#, 2),
+            ("first([1, 2, 3])", 1),
+            ("last([1, 2, 3])", 3),
+            ("len(rest([1, 2, 3]))", 2),
+            ("first(rest([1, 2, 3]))", 2),
+            ("len(push([], 1))", 1),
+            ("last(push([1, 2], 3))", 3),
+            ("let a = [1]; let b = push(a, 2); len(a);", 1),
+        ];
+
+        for (input, expected) in tests {
+            test_integer_object(&test_eval(input), expected);
+        }
+
+        for input in ["first([])", "last([])", "rest([])"] {
+            test_null_object(&test_eval(input));
+        }
+
+        let errors: Vec<(&str, &str)> = vec![
+            ("len(1)", "argument to `len` not supported, got INTEGER"),
+            (
+                r#"len("one", "two")"#,
+                "wrong number of arguments: want=1, got=2",
+            ),
+            ("first(1)", "argument to `first` not supported, got INTEGER"),
+            (
+                "push(1, 1)",
+                "argument to `push` not supported, got INTEGER",
+            ),
+        ];
+
+        for (input, expected) in errors {
+            test_error_object(&test_eval(input), expected);
+        }
+    }
+
+    #[test]
+    fn test_hash_literals() {
+        let input = r#"
+            let two = "two";
+            {
+                "one": 10 - 9,
+                two: 1 + 1,
+                "thr" + "ee": 6 / 2,
+                4: 4,
+                true: 5,
+                false: 6
+            }"#;
+
+        let evaluated = test_eval(input);
+
+        if let Object::Hash(pairs) = &evaluated {
+            let expected: Vec<(Object, i64)> = vec![
+                (Object::String("one".to_string()), 1),
+                (Object::String("two".to_string()), 2),
+                (Object::String("three".to_string()), 3),
+                (Object::Integer(4), 4),
+                (Object::Boolean(true), 5),
+                (Object::Boolean(false), 6),
+            ];
+
+            if pairs.len() != expected.len() {
+                panic!("Hash has wrong number of pairs, got {}", pairs.len());
+            }
+
+            for (key, value) in expected {
+                match pairs.get(&key.hash_key().unwrap()) {
+                    Some(object) => test_integer_object(object, value),
+                    None => panic!("no pair for given key {} in pairs", key.inspect()),
+                }
+            }
+        } else {
+            panic!("Eval didn't return Hash, got {:?}", evaluated);
+        }
+    }
+
+    #[test]
+    fn test_hash_index_expressions() {
+        let tests: Vec<(&str, Option<i64>)> = vec![
+            (r#"{"foo": 5}["foo"]"#, Some(5)),
+            (r#"{"foo": 5}["bar"]"#, None),
+            (r#"let key = "foo"; {"foo": 5}[key]"#, Some(5)),
+            (r#"{}["foo"]"#, None),
+            ("{5: 5}[5]", Some(5)),
+            ("{true: 5}[true]", Some(5)),
+            ("{false: 5}[false]", Some(5)),
+        ];
+
+        for (input, expected) in tests {
+            let evaluated = test_eval(input);
+
+            match expected {
+                Some(value) => test_integer_object(&evaluated, value),
+                None => test_null_object(&evaluated),
+            }
+        }
+    }
+
+    #[test]
+    fn test_unhashable_keys() {
+        let tests: Vec<(&str, &str)> = vec![
+            (
+                r#"{"name": "Mono"}[fn(x) { x }];"#,
+                "unusable as hash key: FUNCTION",
+            ),
+            ("{fn(x) { x }: 1};", "unusable as hash key: FUNCTION"),
+            ("{[1]: 1};", "unusable as hash key: ARRAY"),
+            ("{len: 1};", "unusable as hash key: BUILTIN"),
+        ];
+
+        for (input, expected) in tests {
+            test_error_object(&test_eval(input), expected);
+        }
+    }
+
+    #[test]
+    fn test_error_spans() {
+        let tests: Vec<(&str, &str)> = vec![
+            ("5 + true", "1:1"),
+            ("let x = 1;\nlet y = -true;", "2:9"),
+            ("let f = fn() {\n  missing\n};\nf();", "2:3"),
+            ("len(1, 2)", "1:1"),
+            ("[1][5]", "1:1"),
+            ("let a = 1;\nlet b = 2 * (a / 0);", "2:14"),
+            ("[1, 2 / 0]", "1:5"),
+        ];
+
+        for (input, expected) in tests {
+            let evaluated = test_eval(input);
+
+            if let Object::Error(error) = &evaluated {
+                match error.span {
+                    Some(span) if span.to_string() == expected => {}
+                    span => panic!("{} should fail at {}, got {:?}", input, expected, span),
+                }
+            } else {
+                panic!("no error object returned, got {:?}", evaluated);
+            }
+        }
+    }
+
+    #[test]
+    fn test_short_circuit_evaluation() {
+        let tests: Vec<(&str, bool)> = vec![
+            ("false && missing", false),
+            ("true || missing", true),
+            ("false && 1 / 0", false),
+            ("let called = fn() { puts(\"no\") }; true || called()", true),
+            ("if (false && x[0]) { 1 } else { true }", true),
+        ];
+
+        for (input, expected) in tests {
+            test_boolean_object(&test_eval(input), expected);
+        }
+
+        test_error_object(
+            &test_eval("true && missing"),
+            "identifier not found: missing",
+        );
+        test_error_object(&test_eval("false || -true"), "unknown operator: -BOOLEAN");
+    }
+
+    #[test]
+    fn test_exponent_errors() {
+        test_error_object(&test_eval("2 ** -1"), "negative exponent: 2 ** -1");
+        test_error_object(&test_eval("\"a\" ** 2"), "type mismatch: STRING ** INTEGER");
+        test_error_object(
+            &test_eval("true <= false"),
+            "unknown operator: BOOLEAN <= BOOLEAN",
+        );
+    }
+
+    #[test]
+    fn test_eval_float_expression() {
+        let tests: Vec<(&str, f64)> = vec![
+            ("2.75", 2.75),
+            ("1e-9", 1e-9),
+            ("-2.5", -2.5),
+            ("0.1 + 0.2", 0.30000000000000004),
+            ("10 / 4.0", 2.5),
+            ("10.0 / 4", 2.5),
+            ("1 + 0.5", 1.5),
+            ("2 * 1.5 - 1", 2.0),
+            ("7.5 % 2", 1.5),
+            ("2 ** 0.5", std::f64::consts::SQRT_2),
+            ("4.0 ** 2", 16.0),
+            ("float(10) / 4", 2.5),
+            ("float(\"1.5e3\")", 1500.0),
+        ];
+
+        for (input, expected) in tests {
+            test_float_object(&test_eval(input), expected);
+        }
+
+        // Integer division still truncates.
+        test_integer_object(&test_eval("10 / 4"), 2);
+        test_integer_object(&test_eval("int(2.9)"), 2);
+        test_integer_object(&test_eval("int(-2.9)"), -2);
+
+        let comparisons: Vec<(&str, bool)> = vec![
+            ("1 == 1.0", true),
+            ("1.5 > 1", true),
+            ("2 <= 1.5", false),
+            ("0.1 + 0.2 == 0.3", false),
+            ("1.0 != 1", false),
+        ];
+
+        for (input, expected) in comparisons {
+            test_boolean_object(&test_eval(input), expected);
+        }
+
+        match test_eval("1.0 / 0") {
+            Object::Float(value) if value.is_infinite() => {}
+            other => panic!("Expected inf, got {:?}", other),
+        }
+
+        test_error_object(&test_eval("1.5 + true"), "type mismatch: FLOAT + BOOLEAN");
+        test_error_object(&test_eval("{1.5: 1}"), "unusable as hash key: FLOAT");
+    }
+
+    #[test]
+    fn test_division_by_zero() {
+        let tests: Vec<(&str, &str)> = vec![
+            ("1 / 0", "division by zero: 1 / 0"),
+            ("5 % 0", "division by zero: 5 % 0"),
+            ("let f = fn(x) { x / 0 }; f(3)", "division by zero: 3 / 0"),
+        ];
+
+        for (input, expected) in tests {
+            test_error_object(&test_eval(input), expected);
+        }
+    }
+
+    // With the `bigint` feature these promote to big integers instead.
+    #[cfg(not(feature = "bigint"))]
+    #[test]
+    fn test_integer_overflow() {
+        let tests: Vec<(&str, &str)> = vec![
+            (
+                "9223372036854775807 + 1",
+                "integer overflow: 9223372036854775807 + 1",
+            ),
+            (
+                "-9223372036854775807 - 2",
+                "integer overflow: -9223372036854775807 - 2",
+            ),
+            (
+                "4611686018427387904 * 2",
+                "integer overflow: 4611686018427387904 * 2",
+            ),
+            (
+                "(-9223372036854775807 - 1) / -1",
+                "integer overflow: -9223372036854775808 / -1",
+            ),
+            (
+                "-(-9223372036854775807 - 1)",
+                "integer overflow: -(-9223372036854775808)",
+            ),
+            ("10 ** 19", "integer overflow: 10 ** 19"),
+        ];
+
+        for (input, expected) in tests {
+            test_error_object(&test_eval(input), expected);
+        }
+    }
+
+    #[test]
+    fn test_integer_bounds() {
+        test_integer_object(&test_eval("9223372036854775806 + 1"), i64::MAX);
+        test_integer_object(&test_eval("-9223372036854775807 - 1"), i64::MIN);
+        test_integer_object(&test_eval("-9223372036854775808"), i64::MIN);
+        test_integer_object(&test_eval("-0x8000_0000_0000_0000"), i64::MIN);
+    }
+
+    #[test]
+    fn test_integer_literal_bases() {
+        let tests: Vec<(&str, i64)> = vec![
+            ("0xFF", 255),
+            ("0Xff", 255),
+            ("0b1010", 10),
+            ("0o17", 15),
+            ("1_000_000", 1_000_000),
+            ("0xFFFF_FFFF", 0xFFFF_FFFF),
+            ("0b1111_0000 + 0o7", 247),
+        ];
+
+        for (input, expected) in tests {
+            test_integer_object(&test_eval(input), expected);
+        }
+
+        test_float_object(&test_eval("1_000.5"), 1000.5);
+    }
+}
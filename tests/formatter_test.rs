@@ -0,0 +1,221 @@
+#[cfg(test)]
+mod tests {
+    use mono::{
+        ast::{self, Node},
+        formatter::format,
+        lexer::Lexer,
+        parser::Parser,
+    };
+
+    fn test_format(input: &str, expected: &str) {
+        let formatted = match format(input) {
+            Ok(formatted) => formatted,
+            Err(errors) => panic!("{:?} did not parse: {:?}", input, errors),
+        };
+
+        if formatted != expected {
+            panic!(
+                "Wrong formatting for {:?}\nexpected:\n{}\ngot:\n{}",
+                input, expected, formatted
+            );
+        }
+
+        // Formatting never changes what the code means.
+        if tree(&formatted) != tree(input) {
+            panic!(
+                "{:?} was formatted to {:?}, which parses differently",
+                input, formatted
+            );
+        }
+
+        // Formatting is idempotent.
+        match format(&formatted) {
+            Ok(again) if again == formatted => {}
+            again => panic!("{:?} changed when formatted again: {:?}", formatted, again),
+        }
+    }
+
+    fn parse(input: &str) -> String {
+        let mut parser = Parser::new(Lexer::new(input));
+
+        parser.parse_program().string()
+    }
+
+    // The syntax tree without positions, which formatting moves around.
+    fn tree(input: &str) -> String {
+        let mut parser = Parser::new(Lexer::new(input));
+        let program = parser.parse_program();
+
+        ast::tree(&program)
+            .lines()
+            .map(|line| line.rsplit_once(" @ ").map_or(line, |(node, _)| node))
+            .collect::<Vec<&str>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_layout() {
+        let tests: Vec<(&str, &str)> = vec![
+            ("", ""),
+            ("let   x=5", "let x = 5;\n"),
+            ("let x = 5;let y = x;", "let x = 5;\nlet y = x;\n"),
+            ("return;return x", "return;\nreturn x;\n"),
+            ("puts(1 ,2)", "puts(1, 2);\n"),
+            ("let a = [1,2,  3][0]", "let a = [1, 2, 3][0];\n"),
+            (r#"{"a":1,true:"\n"}"#, "{\"a\": 1, true: \"\\n\"};\n"),
+            ("{}", "{};\n"),
+            ("let f = fn(){}", "let f = fn() {};\n"),
+            ("let f = fn(a,b){a+b;}", "let f = fn(a, b) { a + b };\n"),
+            ("0xFF + 1_000 + 1e-9", "0xFF + 1_000 + 1e-9;\n"),
+            ("if(a){b}", "if (a) { b }\n"),
+            ("if (a) { b }; c", "if (a) { b }\nc;\n"),
+            ("if (false) { 1 };\n-x;", "if (false) { 1 };\n-x;\n"),
+            ("if (true) { 1 };\n[1, 2];", "if (true) { 1 };\n[1, 2];\n"),
+            ("if (a) { b };\n((c));", "if (a) { b }\nc;\n"),
+            (
+                "if (a) { b };\n(c + d) * 2;",
+                "if (a) { b };\n(c + d) * 2;\n",
+            ),
+            ("if (a) { b };\n!c", "if (a) { b };\n!c;\n"),
+            ("if (a) { b };\n{1: 2}", "if (a) { b };\n{1: 2};\n"),
+            (
+                "fn() { if (a) { b }; -c }",
+                "fn() {\n    if (a) { b };\n    -c\n};\n",
+            ),
+            (
+                "if (a) { b } else if (c) { d } else { e }",
+                "if (a) { b } else if (c) { d } else { e }\n",
+            ),
+            (
+                "let f = fn(x) { let y = x * 2; y + 1 };",
+                "let f = fn(x) {\n    let y = x * 2;\n    y + 1\n};\n",
+            ),
+            (
+                "let f = fn(x) { if (x) { return 1; } 2 }",
+                "let f = fn(x) {\n    if (x) {\n        return 1;\n    }\n    2\n};\n",
+            ),
+            (
+                "map(fn(x) { puts(\"a very long string to print out\", x) })",
+                "map(fn(x) {\n    puts(\"a very long string to print out\", x)\n});\n",
+            ),
+            (
+                "let a = 1;\n\n\n\nlet b = 2;\nlet c = 3;",
+                "let a = 1;\n\nlet b = 2;\nlet c = 3;\n",
+            ),
+            (
+                "let f = fn() {\n\n  let a = 1;\n\n  a\n\n};",
+                "let f = fn() {\n    let a = 1;\n\n    a\n};\n",
+            ),
+        ];
+
+        for (input, expected) in tests {
+            test_format(input, expected);
+        }
+    }
+
+    #[test]
+    fn test_minimal_parentheses() {
+        let tests: Vec<(&str, &str)> = vec![
+            ("((a + b) + c)", "a + b + c;\n"),
+            ("a + (b + c)", "a + (b + c);\n"),
+            ("(a * b) + c", "a * b + c;\n"),
+            ("(a + b) * c", "(a + b) * c;\n"),
+            ("a - (b - c)", "a - (b - c);\n"),
+            ("(-a) * b", "-a * b;\n"),
+            ("-(a * b)", "-(a * b);\n"),
+            ("!(a == b)", "!(a == b);\n"),
+            ("2 ** (3 ** 2)", "2 ** 3 ** 2;\n"),
+            ("(2 ** 3) ** 2", "(2 ** 3) ** 2;\n"),
+            ("-(a ** 2)", "-a ** 2;\n"),
+            ("(-a) ** 2", "(-a) ** 2;\n"),
+            (
+                "let x = (-9223372036854775808) ** 2;",
+                "let x = (-9223372036854775808) ** 2;\n",
+            ),
+            ("(-9223372036854775808) + 1", "-9223372036854775808 + 1;\n"),
+            ("(a || b) && c", "(a || b) && c;\n"),
+            ("a || (b && c)", "a || b && c;\n"),
+            ("(a < b) == (c >= d)", "a < b == c >= d;\n"),
+            ("(f)(x)", "f(x);\n"),
+            ("(a + b)(x)", "(a + b)(x);\n"),
+            ("(f(x))[0]", "f(x)[0];\n"),
+            ("(a + b)[0]", "(a + b)[0];\n"),
+            ("f((a + b), (c))", "f(a + b, c);\n"),
+            ("if ((a > b)) { (a) }", "if (a > b) { a }\n"),
+        ];
+
+        for (input, expected) in tests {
+            test_format(input, expected);
+
+            // Dropping parentheses never changes what the code means.
+            let formatted = format(input).unwrap_or_default();
+            if parse(&formatted) != parse(input) {
+                panic!(
+                    "{:?} was formatted to {:?}, which parses differently",
+                    input, formatted
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_comments() {
+        let tests: Vec<(&str, &str)> = vec![
+            ("// only a comment", "// only a comment\n"),
+            (
+                "// header\nlet a = 1; // trailing\n/* block */ let b = 2;",
+                "// header\nlet a = 1; // trailing\n/* block */\nlet b = 2;\n",
+            ),
+            (
+                "/// Doc comment.\n/// More.\nlet f = fn(x) { x };",
+                "/// Doc comment.\n/// More.\nlet f = fn(x) { x };\n",
+            ),
+            (
+                "let f = fn(x) {\n// leading\nx // value\n// closing\n};",
+                "let f = fn(x) {\n    // leading\n    x // value\n    // closing\n};\n",
+            ),
+            (
+                "let f = fn() { /* empty */ };",
+                "let f = fn() {\n    /* empty */\n};\n",
+            ),
+            (
+                "let a = [\n  1, // one\n  2 /* two */\n];",
+                "let a = [1, 2]; // one\n/* two */\n",
+            ),
+            (
+                "if (a) { b } else { /* c */ d }",
+                "if (a) { b } else {\n    /* c */\n    d\n}\n",
+            ),
+            // Comments before a block's `{` stay out of its body.
+            ("if (x /* c */) { 1 }", "if (x) { 1 } /* c */\n"),
+            (
+                "let f = fn(a /* p */, b) { a };",
+                "let f = fn(a, b) { a }; /* p */\n",
+            ),
+            (
+                "let f = fn(a /* p */) {\n  fn(z /* q */) { z } // inner\n};",
+                "let f = fn(a) {\n    fn(z) { z } /* q */ // inner\n}; /* p */\n",
+            ),
+            (
+                "if (a) { b } /* e */ else { c }",
+                "if (a) { b } else { c } /* e */\n",
+            ),
+            (
+                "let a = 1;\n\n// after a blank line\nlet b = 2;",
+                "let a = 1;\n\n// after a blank line\nlet b = 2;\n",
+            ),
+        ];
+
+        for (input, expected) in tests {
+            test_format(input, expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        match format("let x 5;\nlet = 1;") {
+            Err(errors) if errors.len() == 2 => {}
+            other => panic!("Expected two parse errors, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+// Kept apart from repl_test, which turns colors off for the whole test
+// binary while these tests need them on.
+#[cfg(test)]
+mod tests {
+    use colored::Colorize;
+    use mono::repl::highlight;
+
+    #[test]
+    fn test_highlight() {
+        colored::control::set_override(true);
+
+        let tests = vec![
+            (
+                "let x = 1;",
+                format!(
+                    "{} {} {} {}{}",
+                    "let".magenta().bold(),
+                    "x".blue(),
+                    "=".yellow(),
+                    "1".cyan(),
+                    ";".normal()
+                ),
+            ),
+            (
+                "puts(\"hi\") // done",
+                format!(
+                    "{}{}{}{} {}",
+                    "puts".blue(),
+                    "(".normal(),
+                    "\"hi\"".green(),
+                    ")".normal(),
+                    "// done".dimmed()
+                ),
+            ),
+            (
+                "a & \"open",
+                format!("{} {} {}", "a".blue(), "&".red(), "\"open".red()),
+            ),
+            ("/* open", format!("{}", "/* open".red())),
+        ];
+
+        for (input, expected) in tests {
+            let got = highlight(input);
+
+            if got != expected {
+                panic!(
+                    "highlight({:?}) should be {:?}, got {:?}",
+                    input, expected, got
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_highlight_keeps_text() {
+        colored::control::set_override(true);
+
+        for input in [
+            "fn(ä) { ä ** 2 }",
+            "if (x) { 1 } else { 2 }",
+            "\"a\\q\" $ 1.5e3",
+            "",
+        ] {
+            let stripped = strip_escapes(&highlight(input));
+
+            if stripped != input {
+                panic!("highlight({:?}) changed the text to {:?}", input, stripped);
+            }
+        }
+    }
+
+    fn strip_escapes(text: &str) -> String {
+        let mut out = String::new();
+        let mut chars = text.chars();
+
+        while let Some(ch) = chars.next() {
+            if ch == '\x1b' {
+                // Skips up to the `m` that ends a color sequence.
+                for ch in chars.by_ref() {
+                    if ch == 'm' {
+                        break;
+                    }
+                }
+            } else {
+                out.push(ch);
+            }
+        }
+
+        out
+    }
+}
@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
     use mono::lexer::Lexer;
-    use mono::token::TokenType;
+    use mono::token::{TokenType, KEYWORDS};
 
     #[derive(Debug)]
     struct TestType {
@@ -25,7 +25,7 @@ mod tests {
             x + y;
         };
 
-        !-/*5;
+        !-/ *5;
 
         5 < 10 > 5;
 
@@ -36,7 +36,9 @@ mod tests {
         };
 
         10 == 10;
-        10 != 9"
+        10 != 9;
+        [1, 2];
+        {\"foo\": \"bar\"}"
             .to_string();
 
         let tests: Vec<TestType> = vec![
@@ -98,6 +100,18 @@ mod tests {
             TestType::new(TokenType::INT, "10"),
             TestType::new(TokenType::NEQ, "!="),
             TestType::new(TokenType::INT, "9"),
+            TestType::new(TokenType::SEMICOLON, ";"),
+            TestType::new(TokenType::LBRACKET, "["),
+            TestType::new(TokenType::INT, "1"),
+            TestType::new(TokenType::COMMA, ","),
+            TestType::new(TokenType::INT, "2"),
+            TestType::new(TokenType::RBRACKET, "]"),
+            TestType::new(TokenType::SEMICOLON, ";"),
+            TestType::new(TokenType::LBRACE, "{"),
+            TestType::new(TokenType::STRING, "foo"),
+            TestType::new(TokenType::COLON, ":"),
+            TestType::new(TokenType::STRING, "bar"),
+            TestType::new(TokenType::RBRACE, "}"),
             TestType::new(TokenType::EOF, " "),
         ];
 
@@ -121,4 +135,315 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_string_tokens() {
+        let input = r#""foobar" "foo bar" "a\n\tb" "say \"hi\"" "back\\slash" "\u{48}\u{49}" "" "bad \q" "open"#;
+
+        let tests: Vec<TestType> = vec![
+            TestType::new(TokenType::STRING, "foobar"),
+            TestType::new(TokenType::STRING, "foo bar"),
+            TestType::new(TokenType::STRING, "a\n\tb"),
+            TestType::new(TokenType::STRING, "say \"hi\""),
+            TestType::new(TokenType::STRING, "back\\slash"),
+            TestType::new(TokenType::STRING, "HI"),
+            TestType::new(TokenType::STRING, ""),
+            TestType::new(TokenType::ILLEGAL, r#""bad \q""#),
+            TestType::new(TokenType::ILLEGAL, r#""open"#),
+            TestType::new(TokenType::EOF, " "),
+        ];
+
+        let mut lexer = Lexer::new(input);
+
+        for test in tests {
+            let token = lexer.next_token();
+
+            if token.typ != test.expected_type || token.literal != test.expected_literal {
+                panic!(
+                    "Expected {:?} {:?}, got {:?} {:?}",
+                    test.expected_type, test.expected_literal, token.typ, token.literal
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_token_spans() {
+        let input = "let x = 10;\n  \"hi\" == x";
+
+        // (type, start, end, line, column)
+        let tests: Vec<(TokenType, usize, usize, usize, usize)> = vec![
+            (TokenType::LET, 0, 3, 1, 1),
+            (TokenType::IDENT, 4, 5, 1, 5),
+            (TokenType::ASSIGN, 6, 7, 1, 7),
+            (TokenType::INT, 8, 10, 1, 9),
+            (TokenType::SEMICOLON, 10, 11, 1, 11),
+            (TokenType::STRING, 14, 18, 2, 3),
+            (TokenType::EQ, 19, 21, 2, 8),
+            (TokenType::IDENT, 22, 23, 2, 11),
+            (TokenType::EOF, 23, 23, 2, 12),
+            (TokenType::EOF, 23, 23, 2, 12),
+        ];
+
+        let mut lexer = Lexer::new(input);
+
+        for (typ, start, end, line, column) in tests {
+            let token = lexer.next_token();
+            let span = token.span;
+
+            if token.typ != typ
+                || span.start != start
+                || span.end != end
+                || span.line != line
+                || span.column != column
+            {
+                panic!(
+                    "Expected {:?} at {}..{} ({}:{}), got {:?} at {}..{} ({}:{})",
+                    typ,
+                    start,
+                    end,
+                    line,
+                    column,
+                    token.typ,
+                    span.start,
+                    span.end,
+                    span.line,
+                    span.column
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_unicode_tokens() {
+        let input = "let año = \"¿Qué tal, niño?\";\nañadir(año) ¡";
+
+        // (type, literal, start, end, line, column)
+        let tests: Vec<(TokenType, &str, usize, usize, usize, usize)> = vec![
+            (TokenType::LET, "let", 0, 3, 1, 1),
+            (TokenType::IDENT, "año", 4, 8, 1, 5),
+            (TokenType::ASSIGN, "=", 9, 10, 1, 9),
+            (TokenType::STRING, "¿Qué tal, niño?", 11, 31, 1, 11),
+            (TokenType::SEMICOLON, ";", 31, 32, 1, 28),
+            (TokenType::IDENT, "añadir", 33, 40, 2, 1),
+            (TokenType::LPAREN, "(", 40, 41, 2, 7),
+            (TokenType::IDENT, "año", 41, 45, 2, 8),
+            (TokenType::RPAREN, ")", 45, 46, 2, 11),
+            (TokenType::ILLEGAL, "¡", 47, 49, 2, 13),
+            (TokenType::EOF, " ", 49, 49, 2, 14),
+        ];
+
+        let mut lexer = Lexer::new(input);
+
+        for (typ, literal, start, end, line, column) in tests {
+            let token = lexer.next_token();
+            let span = token.span;
+
+            if token.typ != typ
+                || token.literal != literal
+                || (span.start, span.end, span.line, span.column) != (start, end, line, column)
+            {
+                panic!(
+                    "Expected {:?} {:?} at {}..{} ({}:{}), got {:?} {:?} at {:?}",
+                    typ, literal, start, end, line, column, token.typ, token.literal, span
+                );
+            }
+
+            if &input[span.start..span.end] != literal && typ == TokenType::IDENT {
+                panic!("Span {:?} does not cover {:?}", span, literal);
+            }
+        }
+    }
+
+    fn token_types(input: &str) -> Vec<(TokenType, String)> {
+        let mut lexer = Lexer::new(input);
+        let mut tokens = Vec::new();
+
+        loop {
+            let token = lexer.next_token();
+            if token.typ == TokenType::EOF {
+                return tokens;
+            }
+            tokens.push((token.typ, token.literal));
+        }
+    }
+
+    #[test]
+    fn test_comments() {
+        let tests: Vec<(&str, Vec<(TokenType, &str)>)> = vec![
+            (
+                "let x = 1; // the answer\nx / 2 // trailing",
+                vec![
+                    (TokenType::LET, "let"),
+                    (TokenType::IDENT, "x"),
+                    (TokenType::ASSIGN, "="),
+                    (TokenType::INT, "1"),
+                    (TokenType::SEMICOLON, ";"),
+                    (TokenType::IDENT, "x"),
+                    (TokenType::SLASH, "/"),
+                    (TokenType::INT, "2"),
+                ],
+            ),
+            (
+                "a /* uno /* dos */ sigue */ + b",
+                vec![
+                    (TokenType::IDENT, "a"),
+                    (TokenType::PLUS, "+"),
+                    (TokenType::IDENT, "b"),
+                ],
+            ),
+            ("/**/1/***/", vec![(TokenType::INT, "1")]),
+            (
+                "1 /* nunca /* cerrado */",
+                vec![(TokenType::INT, "1"), (TokenType::ILLEGAL, "/*")],
+            ),
+            ("//", vec![]),
+        ];
+
+        for (input, expected) in tests {
+            let tokens = token_types(input);
+            let expected: Vec<(TokenType, String)> = expected
+                .into_iter()
+                .map(|(typ, literal)| (typ, literal.to_string()))
+                .collect();
+
+            if tokens != expected {
+                panic!(
+                    "Wrong tokens for {:?}\nexpected {:?}\ngot      {:?}",
+                    input, expected, tokens
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_comment_spans() {
+        let mut lexer = Lexer::new("// uno\n/* dos\n tres */ x");
+        let token = lexer.next_token();
+
+        if token.typ != TokenType::IDENT || (token.span.line, token.span.column) != (3, 10) {
+            panic!("Expected IDENT at 3:10, got {:?}", token);
+        }
+    }
+
+    #[test]
+    fn test_doc_comments() {
+        let mut lexer = Lexer::new("/// Suma dos números.\n///\n//// no\n// tampoco\nlet");
+
+        let token = lexer.next_token();
+        let docs = lexer.take_doc_comments();
+
+        if token.typ != TokenType::LET || docs != vec!["Suma dos números.", ""] {
+            panic!("Expected LET with docs, got {:?} {:?}", token, docs);
+        }
+
+        lexer.next_token();
+        if !lexer.take_doc_comments().is_empty() {
+            panic!("Doc comments were returned twice");
+        }
+    }
+
+    #[test]
+    fn test_operator_tokens() {
+        let tokens = token_types("<= >= < > % ** * && || & |");
+        let expected: Vec<(TokenType, String)> = vec![
+            (TokenType::LTE, "<="),
+            (TokenType::GTE, ">="),
+            (TokenType::LT, "<"),
+            (TokenType::GT, ">"),
+            (TokenType::PERCENT, "%"),
+            (TokenType::POWER, "**"),
+            (TokenType::ASTERISK, "*"),
+            (TokenType::AND, "&&"),
+            (TokenType::OR, "||"),
+            (TokenType::ILLEGAL, "&"),
+            (TokenType::ILLEGAL, "|"),
+        ]
+        .into_iter()
+        .map(|(typ, literal)| (typ, literal.to_string()))
+        .collect();
+
+        if tokens != expected {
+            panic!("Expected {:?}, got {:?}", expected, tokens);
+        }
+    }
+
+    #[test]
+    fn test_number_tokens() {
+        let tokens = token_types("1 2.75 1e-9 2E+10 6.02e23 1.e 1e x.0 4.5.6");
+        let expected: Vec<(TokenType, String)> = vec![
+            (TokenType::INT, "1"),
+            (TokenType::FLOAT, "2.75"),
+            (TokenType::FLOAT, "1e-9"),
+            (TokenType::FLOAT, "2E+10"),
+            (TokenType::FLOAT, "6.02e23"),
+            (TokenType::INT, "1"),
+            (TokenType::ILLEGAL, "."),
+            (TokenType::IDENT, "e"),
+            (TokenType::INT, "1"),
+            (TokenType::IDENT, "e"),
+            (TokenType::IDENT, "x"),
+            (TokenType::ILLEGAL, "."),
+            (TokenType::INT, "0"),
+            (TokenType::FLOAT, "4.5"),
+            (TokenType::ILLEGAL, "."),
+            (TokenType::INT, "6"),
+        ]
+        .into_iter()
+        .map(|(typ, literal)| (typ, literal.to_string()))
+        .collect();
+
+        if tokens != expected {
+            panic!("Expected {:?}, got {:?}", expected, tokens);
+        }
+    }
+
+    #[test]
+    fn test_integer_bases() {
+        let tokens = token_types("0xFF 0b1010 0o17 1_000 0b102 0x 1_000.5 0.5");
+        let expected: Vec<(TokenType, String)> = vec![
+            (TokenType::INT, "0xFF"),
+            (TokenType::INT, "0b1010"),
+            (TokenType::INT, "0o17"),
+            (TokenType::INT, "1_000"),
+            (TokenType::INT, "0b102"),
+            (TokenType::INT, "0x"),
+            (TokenType::FLOAT, "1_000.5"),
+            (TokenType::FLOAT, "0.5"),
+        ]
+        .into_iter()
+        .map(|(typ, literal)| (typ, literal.to_string()))
+        .collect();
+
+        if tokens != expected {
+            panic!("Expected {:?}, got {:?}", expected, tokens);
+        }
+    }
+
+    #[test]
+    fn test_keywords_list() {
+        for keyword in KEYWORDS {
+            if TokenType::serialize(keyword) == TokenType::IDENT {
+                panic!("{} is listed as a keyword but lexes as IDENT", keyword);
+            }
+        }
+    }
+
+    #[test]
+    fn test_with_offset() {
+        let mut lexer = Lexer::with_offset("a\n  bc", 10);
+        lexer.next_token();
+        let token = lexer.next_token();
+
+        // Offsets move, but lines and columns are within the input.
+        let span = (
+            token.span.start,
+            token.span.end,
+            token.span.line,
+            token.span.column,
+        );
+        if span != (14, 16, 2, 3) {
+            panic!("Wrong span for {:?}: {:?}", token.literal, span);
+        }
+    }
 }
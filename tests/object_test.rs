@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use mono::object::{Arity, Builtin, HashKey, Object};
+
+    #[test]
+    fn test_inspect() {
+        let mut pairs = BTreeMap::new();
+        pairs.insert(HashKey::String("one".to_string()), Object::Integer(1));
+        pairs.insert(HashKey::Boolean(true), Object::Null);
+
+        let tests: Vec<(Object, &str)> = vec![
+            (Object::Integer(-5), "-5"),
+            (Object::Boolean(true), "true"),
+            (Object::String("hello\n".to_string()), "\"hello\\n\""),
+            (Object::Null, "null"),
+            (Object::ReturnValue(Box::new(Object::Integer(10))), "10"),
+            (
+                Object::error("identifier not found: x"),
+                "ERROR: identifier not found: x",
+            ),
+            (
+                Object::Array(vec![
+                    Object::Integer(1),
+                    Object::String("two".to_string()),
+                    Object::Boolean(false),
+                ]),
+                "[1, \"two\", false]",
+            ),
+            (Object::Hash(pairs), "{true: null, \"one\": 1}"),
+            (
+                Object::Builtin(Builtin {
+                    name: "len".to_string(),
+                    arity: Arity::Exact(1),
+                    function: |_| Ok(Object::Null),
+                }),
+                "builtin function len",
+            ),
+        ];
+
+        for (object, expected) in tests {
+            if object.inspect() != expected {
+                panic!("Expected {}, got {}", expected, object.inspect());
+            }
+        }
+    }
+
+    #[test]
+    fn test_type_name() {
+        let tests: Vec<(Object, &str)> = vec![
+            (Object::Integer(1), "INTEGER"),
+            (Object::Boolean(false), "BOOLEAN"),
+            (Object::String(String::new()), "STRING"),
+            (Object::Null, "NULL"),
+            (Object::Array(Vec::new()), "ARRAY"),
+            (Object::Hash(BTreeMap::new()), "HASH"),
+        ];
+
+        for (object, expected) in tests {
+            if object.type_name() != expected {
+                panic!("Expected {}, got {}", expected, object.type_name());
+            }
+        }
+    }
+
+    #[test]
+    fn test_hash_key() {
+        let hashable = vec![
+            Object::Integer(1),
+            Object::Boolean(true),
+            Object::String("name".to_string()),
+        ];
+
+        for object in hashable {
+            match object.hash_key() {
+                Some(key) => {
+                    if key.to_object().inspect() != object.inspect() {
+                        panic!("Hash key does not round trip, got {:?}", key);
+                    }
+                }
+                None => panic!("{} should be hashable", object.inspect()),
+            }
+        }
+
+        for object in [Object::Null, Object::Array(Vec::new())] {
+            if object.hash_key().is_some() {
+                panic!("{} should not be hashable", object.type_name());
+            }
+        }
+
+        let first = Object::String("key".to_string()).hash_key();
+        let second = Object::String("key".to_string()).hash_key();
+        if first != second {
+            panic!("Equal strings produced different hash keys");
+        }
+    }
+}
@@ -5,10 +5,7 @@ mod tests {
     use std::vec;
 
     use mono::{
-        ast::{
-            ExpressionStatement, ExpressionVariants, InfixExpression, Node, Program,
-            StatementVariant,
-        },
+        ast::{ExpressionStatement, ExpressionVariants, Node, Program, StatementVariant},
         lexer::Lexer,
         parser::Parser,
     };
@@ -37,7 +34,7 @@ mod tests {
                 Self {
                     input: input.to_string(),
                     expected_identifier: expected_identifier.to_string(),
-                    expected_value: expected_value,
+                    expected_value,
                 }
             }
         }
@@ -91,23 +88,20 @@ mod tests {
             return false;
         }
 
-        match statement {
-            StatementVariant::Let(s) => {
-                if s.name.value != name {
-                    dbg!(
-                        "statement.name.value is not {}, got {}",
-                        name,
-                        s.name.value.clone()
-                    );
-                    return false;
-                }
+        if let StatementVariant::Let(s) = statement {
+            if s.name.value != name {
+                dbg!(
+                    "statement.name.value is not {}, got {}",
+                    name,
+                    s.name.value.clone()
+                );
+                return false;
+            }
 
-                if s.name.token_literal() != name {
-                    dbg!("statement.name  is not {}, got {:?}", name, &s.name);
-                    return false;
-                }
+            if s.name.token_literal() != name {
+                dbg!("statement.name  is not {}, got {:?}", name, &s.name);
+                return false;
             }
-            _ => {}
         }
         if let StatementVariant::Let(let_statement) = statement {
             if let_statement.name.value != name {
@@ -122,16 +116,16 @@ mod tests {
     }
 
     fn check_parser_errors(parser: Parser) {
-        let errors = parser.errors().clone();
+        let errors = parser.errors();
 
-        if errors.len() == 0 {
+        if errors.is_empty() {
             return;
         }
 
         eprint!("\nParser has {} errors.\n", errors.len());
 
-        for message in errors {
-            eprint!("\nParser error: {:?}", message);
+        for diagnostic in errors {
+            eprint!("\nParser error: {}", diagnostic);
         }
 
         panic!();
@@ -139,34 +133,51 @@ mod tests {
 
     #[test]
     fn test_return_statements() {
-        let input = "
-            return 5;
-            return 10;
-            return 15;
-        ";
+        let tests: Vec<(&str, Option<Expected>)> = vec![
+            ("return 5;", Some(Expected::Int(5))),
+            ("return true;", Some(Expected::Boolean(true))),
+            (
+                "return foobar;",
+                Some(Expected::String("foobar".to_string())),
+            ),
+            ("return;", None),
+            ("return", None),
+        ];
 
-        let program = create_parse_program(input);
+        for (input, expected) in tests {
+            let program = create_parse_program(input);
 
-        if program.statements.len() != 3 {
-            panic!(
-                "program.statements does not contain 3 statements. Got {:?}",
-                program.statements.leak()
-            );
-        }
+            if program.statements.len() != 1 {
+                panic!(
+                    "program.statements does not contain 1 statement. Got {}",
+                    program.statements.len()
+                );
+            }
 
-        for statement in program.statements {
-            match statement {
-                StatementVariant::Return(s) => {
-                    if s.token_literal() != "return" {
-                        panic!(
-                            "statement.token_literal is not 'return', got {}",
-                            s.token_literal()
-                        );
-                    }
+            if let StatementVariant::Return(s) = &program.statements[0] {
+                if s.token_literal() != "return" {
+                    panic!(
+                        "statement.token_literal is not 'return', got {}",
+                        s.token_literal()
+                    );
                 }
-                _ => {
-                    eprint!("statement is not ReturnStatement, got {:?}", statement);
+
+                match (&s.return_value, expected) {
+                    (Some(value), Some(expected)) => {
+                        if !test_literal_expression(value.clone(), expected) {
+                            panic!();
+                        }
+                    }
+                    (None, None) => {}
+                    (value, expected) => {
+                        panic!("Expected return value {:?}, got {:?}", expected, value);
+                    }
                 }
+            } else {
+                panic!(
+                    "statement is not ReturnStatement, got {:?}",
+                    program.statements[0]
+                );
             }
         }
     }
@@ -234,11 +245,9 @@ mod tests {
 
             for statement in program.statements {
                 if let StatementVariant::Expression(exp) = statement {
-                    if let Some(expression_variant) = &exp.expression {
-                        if let ExpressionVariants::Boolean(boolean) = expression_variant {
-                            if boolean.value != test.expected_boolean_value {
-                                panic!("boolean.value is not true, got {}", boolean.value);
-                            }
+                    if let Some(ExpressionVariants::Boolean(boolean)) = &exp.expression {
+                        if boolean.value != test.expected_boolean_value {
+                            panic!("boolean.value is not true, got {}", boolean.value);
                         }
                     }
                 }
@@ -516,7 +525,7 @@ mod tests {
             OpPrecedenceTest::new("a * b / c", "((a * b) / c)"),
             OpPrecedenceTest::new("a + b / c", "(a + (b / c))"),
             OpPrecedenceTest::new("a + b * c + d / e - f", "(((a + (b * c)) + (d / e)) - f)"),
-            OpPrecedenceTest::new("3 + 4; -5 * 5", "(3 + 4)((-5) * 5)"),
+            OpPrecedenceTest::new("3 + 4; -5 * 5", "(3 + 4);((-5) * 5)"),
             OpPrecedenceTest::new("5 > 4 == 3 < 4", "((5 > 4) == (3 < 4))"),
             OpPrecedenceTest::new("true", "true"),
             OpPrecedenceTest::new("false", "false"),
@@ -527,6 +536,45 @@ mod tests {
             OpPrecedenceTest::new("2 / (5 + 5)", "(2 / (5 + 5))"),
             OpPrecedenceTest::new("-(5 + 5)", "(-(5 + 5))"),
             OpPrecedenceTest::new("!(true == true)", "(!(true == true))"),
+            OpPrecedenceTest::new("a + add(b * c) + d", "((a + add((b * c))) + d)"),
+            OpPrecedenceTest::new("let x = 1 + 2 * 3;", "let x = (1 + (2 * 3));"),
+            OpPrecedenceTest::new(
+                "a * [1, 2, 3, 4][b * c] * d",
+                "((a * ([1, 2, 3, 4][(b * c)])) * d)",
+            ),
+            OpPrecedenceTest::new(
+                "add(a * b[2], b[1], 2 * [1, 2][1])",
+                "add((a * (b[2])), (b[1]), (2 * ([1, 2][1])))",
+            ),
+            OpPrecedenceTest::new("f(x)[0]", "(f(x)[0])"),
+            OpPrecedenceTest::new("return -a * b;", "return ((-a) * b);"),
+            OpPrecedenceTest::new(
+                "if (x < y) { x } else { y }",
+                "if ((x < y)) { x } else { y }",
+            ),
+            OpPrecedenceTest::new(
+                "if (a) { 1 } else if (b) { 2 } else { 3 }",
+                "if (a) { 1 } else { if (b) { 2 } else { 3 } }",
+            ),
+            OpPrecedenceTest::new(
+                "add(a, b, 1, 2 * 3, 4 + 5, add(6, 7 * 8))",
+                "add(a, b, 1, (2 * 3), (4 + 5), add(6, (7 * 8)))",
+            ),
+            OpPrecedenceTest::new(
+                "add(a + b + c * d / f + g)",
+                "add((((a + b) + ((c * d) / f)) + g))",
+            ),
+            OpPrecedenceTest::new("a <= b == b >= a", "((a <= b) == (b >= a))"),
+            OpPrecedenceTest::new("a + b % c * d", "(a + ((b % c) * d))"),
+            OpPrecedenceTest::new("a || b && c", "(a || (b && c))"),
+            OpPrecedenceTest::new("a && b || c && d", "((a && b) || (c && d))"),
+            OpPrecedenceTest::new("a == b && c < d", "((a == b) && (c < d))"),
+            OpPrecedenceTest::new("!a && b", "((!a) && b)"),
+            OpPrecedenceTest::new("2 ** 3 ** 2", "(2 ** (3 ** 2))"),
+            OpPrecedenceTest::new("a * b ** c", "(a * (b ** c))"),
+            OpPrecedenceTest::new("-a ** b", "(-(a ** b))"),
+            OpPrecedenceTest::new("a ** -b", "(a ** (-b))"),
+            OpPrecedenceTest::new("f(a) ** a[0]", "(f(a) ** (a[0]))"),
         ];
 
         for test in tests {
@@ -564,7 +612,7 @@ mod tests {
             if bool_exp.token_literal() != format!("{}", value) {
                 eprintln!(
                     "boolean.token_literal() not {}, got {}",
-                    format!("{}", value),
+                    value,
                     bool_exp.token_literal()
                 );
                 return false;
@@ -584,7 +632,7 @@ mod tests {
         right: Expected,
     ) -> bool {
         if let ExpressionVariants::Infix(inf_exp) = expression {
-            if !test_literal_expression(ExpressionVariants::Infix(inf_exp.clone()), left) {
+            if !test_literal_expression(*inf_exp.left, left) {
                 return false;
             }
 
@@ -603,7 +651,7 @@ mod tests {
             true
         } else {
             eprintln!("expression is not InfixExpression, got {:?}", expression);
-            return false;
+            false
         }
     }
 
@@ -652,7 +700,7 @@ mod tests {
                     );
                 }
 
-                if let Some(_) = if_exp.alternative {
+                if if_exp.alternative.is_some() {
                     panic!(
                         "if_exp.alternative was not None, got {:?}",
                         if_exp.alternative
@@ -671,4 +719,830 @@ mod tests {
             )
         }
     }
+
+    #[test]
+    fn test_function_literal_parsing() {
+        let input = "fn(x, y) { x + y; }";
+
+        let program = create_parse_program(input);
+
+        if program.statements.len() != 1 {
+            panic!(
+                "program.statements does not contain 1 statement, got {}",
+                program.statements.len()
+            );
+        }
+
+        if let StatementVariant::Expression(expr_stmt) = &program.statements[0] {
+            if let Some(ExpressionVariants::Function(function)) = &expr_stmt.expression {
+                if function.parameters.len() != 2 {
+                    panic!(
+                        "function literal parameters wrong, want 2, got {}",
+                        function.parameters.len()
+                    );
+                }
+
+                if function.parameters[0].value != "x" || function.parameters[1].value != "y" {
+                    panic!("function parameters wrong, got {:?}", function.parameters);
+                }
+
+                if function.body.statements.len() != 1 {
+                    panic!(
+                        "function.body.statements has not 1 statement, got {}",
+                        function.body.statements.len()
+                    );
+                }
+
+                if let StatementVariant::Expression(body_stmt) = &function.body.statements[0] {
+                    if !test_infix_expression(
+                        body_stmt.expression.clone().unwrap(),
+                        Expected::String("x".to_string()),
+                        "+",
+                        Expected::String("y".to_string()),
+                    ) {
+                        panic!();
+                    }
+                } else {
+                    panic!(
+                        "function body statement is not an ExpressionStatement, got {:?}",
+                        function.body.statements[0]
+                    );
+                }
+            } else {
+                panic!(
+                    "expression is not a FunctionLiteral, got {:?}",
+                    expr_stmt.expression
+                );
+            }
+        } else {
+            panic!(
+                "program.statements[0] is not an ExpressionStatement, got {:?}",
+                program.statements[0]
+            );
+        }
+    }
+
+    #[test]
+    fn test_function_parameter_parsing() {
+        let tests: Vec<(&str, Vec<&str>)> = vec![
+            ("fn() {};", vec![]),
+            ("fn(x) {};", vec!["x"]),
+            ("fn(x, y, z) {};", vec!["x", "y", "z"]),
+        ];
+
+        for (input, expected_parameters) in tests {
+            let program = create_parse_program(input);
+
+            if let StatementVariant::Expression(expr_stmt) = &program.statements[0] {
+                if let Some(ExpressionVariants::Function(function)) = &expr_stmt.expression {
+                    let parameters: Vec<&str> = function
+                        .parameters
+                        .iter()
+                        .map(|p| p.value.as_str())
+                        .collect();
+
+                    if parameters != expected_parameters {
+                        panic!(
+                            "Expected parameters {:?}, got {:?}",
+                            expected_parameters, parameters
+                        );
+                    }
+                } else {
+                    panic!(
+                        "expression is not a FunctionLiteral, got {:?}",
+                        expr_stmt.expression
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_call_expression_parsing() {
+        let input = "add(1, 2 * 3, 4 + 5);";
+
+        let program = create_parse_program(input);
+
+        if program.statements.len() != 1 {
+            panic!(
+                "program.statements does not contain 1 statement, got {}",
+                program.statements.len()
+            );
+        }
+
+        if let StatementVariant::Expression(expr_stmt) = &program.statements[0] {
+            if let Some(ExpressionVariants::Call(call)) = &expr_stmt.expression {
+                if !test_identifier(*call.function.clone(), "add") {
+                    panic!();
+                }
+
+                if call.arguments.len() != 3 {
+                    panic!("wrong length of arguments, got {}", call.arguments.len());
+                }
+
+                if !test_integer_literal(call.arguments[0].clone(), 1) {
+                    panic!();
+                }
+
+                if !test_infix_expression(
+                    call.arguments[1].clone(),
+                    Expected::Int(2),
+                    "*",
+                    Expected::Int(3),
+                ) {
+                    panic!();
+                }
+
+                if !test_infix_expression(
+                    call.arguments[2].clone(),
+                    Expected::Int(4),
+                    "+",
+                    Expected::Int(5),
+                ) {
+                    panic!();
+                }
+            } else {
+                panic!(
+                    "expression is not a CallExpression, got {:?}",
+                    expr_stmt.expression
+                );
+            }
+        } else {
+            panic!(
+                "program.statements[0] is not an ExpressionStatement, got {:?}",
+                program.statements[0]
+            );
+        }
+    }
+
+    #[test]
+    fn test_if_else_expression() {
+        let input = "if (x < y) { x } else { y }";
+
+        let program = create_parse_program(input);
+
+        if program.statements.len() != 1 {
+            panic!(
+                "program.statements does not contain 1 statement, got {}",
+                program.statements.len()
+            );
+        }
+
+        if let StatementVariant::Expression(expr_stmt) = &program.statements[0] {
+            if let Some(ExpressionVariants::If(if_exp)) = &expr_stmt.expression {
+                if !test_infix_expression(
+                    *if_exp.condition.clone(),
+                    Expected::String("x".to_string()),
+                    "<",
+                    Expected::String("y".to_string()),
+                ) {
+                    panic!();
+                }
+
+                if let Some(alternative) = &if_exp.alternative {
+                    if alternative.statements.len() != 1 {
+                        panic!(
+                            "alternative is not 1 statement, got {}",
+                            alternative.statements.len()
+                        );
+                    }
+
+                    if let StatementVariant::Expression(alt) = &alternative.statements[0] {
+                        if !test_identifier(alt.expression.clone().unwrap(), "y") {
+                            panic!();
+                        }
+                    } else {
+                        panic!(
+                            "alternative.statements[0] is not an Expression statement, got {:?}",
+                            alternative.statements[0]
+                        );
+                    }
+                } else {
+                    panic!("if_exp.alternative was None");
+                }
+            } else {
+                panic!(
+                    "expression_statement is not IfExpression, got {:?}",
+                    expr_stmt
+                );
+            }
+        } else {
+            panic!(
+                "program.statements[0] is not an ExpressionStatement, got {:?}",
+                program.statements[0]
+            )
+        }
+    }
+
+    #[test]
+    fn test_else_if_expression() {
+        let input = "if (a) { 1 } else if (b) { 2 } else { 3 }";
+
+        let program = create_parse_program(input);
+
+        if let StatementVariant::Expression(expr_stmt) = &program.statements[0] {
+            if let Some(ExpressionVariants::If(if_exp)) = &expr_stmt.expression {
+                let alternative = if_exp
+                    .alternative
+                    .as_ref()
+                    .expect("if_exp.alternative was None");
+
+                if let StatementVariant::Expression(nested) = &alternative.statements[0] {
+                    if let Some(ExpressionVariants::If(nested_if)) = &nested.expression {
+                        if !test_identifier(*nested_if.condition.clone(), "b") {
+                            panic!();
+                        }
+
+                        if nested_if.alternative.is_none() {
+                            panic!("nested if has no else branch");
+                        }
+                    } else {
+                        panic!("else if branch is not an IfExpression, got {:?}", nested);
+                    }
+                }
+            } else {
+                panic!(
+                    "expression_statement is not IfExpression, got {:?}",
+                    expr_stmt
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_string_literal_expression() {
+        let input = r#""hello\tworld";"#;
+
+        let program = create_parse_program(input);
+
+        if let StatementVariant::Expression(expr_stmt) = &program.statements[0] {
+            if let Some(ExpressionVariants::String(literal)) = &expr_stmt.expression {
+                if literal.value != "hello\tworld" {
+                    panic!(
+                        "literal.value not \"hello\\tworld\", got {:?}",
+                        literal.value
+                    );
+                }
+
+                if literal.string() != r#""hello\tworld""# {
+                    panic!(
+                        "literal.string() did not re-escape, got {}",
+                        literal.string()
+                    );
+                }
+            } else {
+                panic!(
+                    "expression is not a StringLiteral, got {:?}",
+                    expr_stmt.expression
+                );
+            }
+        } else {
+            panic!(
+                "program.statements[0] is not an ExpressionStatement, got {:?}",
+                program.statements[0]
+            );
+        }
+    }
+
+    #[test]
+    fn test_parsing_array_literals() {
+        let input = "[1, 2 * 2, 3 + 3]";
+
+        let program = create_parse_program(input);
+
+        if let StatementVariant::Expression(expr_stmt) = &program.statements[0] {
+            if let Some(ExpressionVariants::Array(array)) = &expr_stmt.expression {
+                if array.elements.len() != 3 {
+                    panic!("array.elements.len() not 3, got {}", array.elements.len());
+                }
+
+                if !test_integer_literal(array.elements[0].clone(), 1)
+                    || !test_infix_expression(
+                        array.elements[1].clone(),
+                        Expected::Int(2),
+                        "*",
+                        Expected::Int(2),
+                    )
+                    || !test_infix_expression(
+                        array.elements[2].clone(),
+                        Expected::Int(3),
+                        "+",
+                        Expected::Int(3),
+                    )
+                {
+                    panic!();
+                }
+            } else {
+                panic!(
+                    "expression is not an ArrayLiteral, got {:?}",
+                    expr_stmt.expression
+                );
+            }
+        }
+
+        let program = create_parse_program("[]");
+        if program.string() != "[]" {
+            panic!("Expected [], got {}", program.string());
+        }
+    }
+
+    #[test]
+    fn test_parsing_index_expressions() {
+        let input = "myArray[1 + 1]";
+
+        let program = create_parse_program(input);
+
+        if let StatementVariant::Expression(expr_stmt) = &program.statements[0] {
+            if let Some(ExpressionVariants::Index(index)) = &expr_stmt.expression {
+                if !test_identifier(*index.left.clone(), "myArray") {
+                    panic!();
+                }
+
+                if !test_infix_expression(
+                    *index.index.clone(),
+                    Expected::Int(1),
+                    "+",
+                    Expected::Int(1),
+                ) {
+                    panic!();
+                }
+            } else {
+                panic!(
+                    "expression is not an IndexExpression, got {:?}",
+                    expr_stmt.expression
+                );
+            }
+        }
+    }
+
+    fn parse_hash_literal(input: &str) -> mono::ast::HashLiteral {
+        let program = create_parse_program(input);
+
+        if let StatementVariant::Expression(expr_stmt) = &program.statements[0] {
+            if let Some(ExpressionVariants::Hash(hash)) = &expr_stmt.expression {
+                return hash.clone();
+            }
+        }
+
+        panic!(
+            "program.statements[0] is not a HashLiteral, got {:?}",
+            program.statements[0]
+        );
+    }
+
+    #[test]
+    fn test_parsing_hash_literals_string_keys() {
+        let hash = parse_hash_literal(r#"{"one": 1, "two": 2, "three": 3}"#);
+
+        let expected: Vec<(&str, i64)> = vec![("one", 1), ("two", 2), ("three", 3)];
+
+        if hash.pairs.len() != expected.len() {
+            panic!("hash.pairs has wrong length, got {}", hash.pairs.len());
+        }
+
+        for ((key, value), (expected_key, expected_value)) in hash.pairs.iter().zip(expected) {
+            if let ExpressionVariants::String(literal) = key {
+                if literal.value != expected_key {
+                    panic!("key is not {}, got {}", expected_key, literal.value);
+                }
+            } else {
+                panic!("key is not a StringLiteral, got {:?}", key);
+            }
+
+            if !test_integer_literal(value.clone(), expected_value) {
+                panic!();
+            }
+        }
+    }
+
+    #[test]
+    fn test_parsing_empty_hash_literal() {
+        let hash = parse_hash_literal("{}");
+
+        if !hash.pairs.is_empty() {
+            panic!("hash.pairs is not empty, got {:?}", hash.pairs);
+        }
+    }
+
+    #[test]
+    fn test_parsing_hash_literals_with_expressions() {
+        let hash = parse_hash_literal(r#"{"one": 0 + 1, true: 10 - 8, 3: 15 / 5}"#);
+
+        let expected: Vec<(i64, &str, i64)> = vec![(0, "+", 1), (10, "-", 8), (15, "/", 5)];
+
+        for ((_, value), (left, operator, right)) in hash.pairs.iter().zip(expected) {
+            if !test_infix_expression(
+                value.clone(),
+                Expected::Int(left),
+                operator,
+                Expected::Int(right),
+            ) {
+                panic!();
+            }
+        }
+
+        if !test_boolean_literal(hash.pairs[1].0.clone(), true)
+            || !test_integer_literal(hash.pairs[2].0.clone(), 3)
+        {
+            panic!();
+        }
+    }
+
+    #[test]
+    fn test_hash_literal_in_blocks() {
+        let program = create_parse_program(r#"fn() { {"a": 1} }"#);
+
+        if program.string() != r#"fn() { {"a": 1} }"# {
+            panic!(
+                "Expected hash inside function body, got {}",
+                program.string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_node_spans() {
+        let input = "let add = fn(a, b) {\n  a + b\n};\nadd(1, [2][0]);";
+
+        let program = create_parse_program(input);
+
+        let slice = |node: &dyn Node| {
+            let span = node.span();
+            input[span.start..span.end].to_string()
+        };
+
+        if slice(&program) != input {
+            panic!(
+                "Program span does not cover the input, got {:?}",
+                slice(&program)
+            );
+        }
+
+        if let StatementVariant::Let(let_stmt) = &program.statements[0] {
+            if slice(&program.statements[0]) != "let add = fn(a, b) {\n  a + b\n};" {
+                panic!("Wrong let span, got {:?}", slice(&program.statements[0]));
+            }
+
+            if let Some(ExpressionVariants::Function(function)) = &let_stmt.value {
+                let body = &function.body.statements[0];
+                if slice(body) != "a + b" {
+                    panic!("Wrong body span, got {:?}", slice(body));
+                }
+
+                if body.span().line != 2 || body.span().column != 3 {
+                    panic!("Wrong body location, got {}", body.span());
+                }
+            } else {
+                panic!(
+                    "let value is not a FunctionLiteral, got {:?}",
+                    let_stmt.value
+                );
+            }
+        }
+
+        if let StatementVariant::Expression(expr_stmt) = &program.statements[1] {
+            if let Some(ExpressionVariants::Call(call)) = &expr_stmt.expression {
+                if slice(call) != "add(1, [2][0])" {
+                    panic!("Wrong call span, got {:?}", slice(call));
+                }
+
+                if slice(&call.arguments[1]) != "[2][0]" {
+                    panic!("Wrong index span, got {:?}", slice(&call.arguments[1]));
+                }
+            } else {
+                panic!("expression is not a CallExpression, got {:?}", expr_stmt);
+            }
+        }
+    }
+
+    #[test]
+    fn test_error_locations() {
+        let tests: Vec<(&str, &str)> = vec![
+            (
+                "let x 5;",
+                "1:7: Expected next token to be ASSIGN, got INT instead",
+            ),
+            (
+                "let x = 1;\nlet = 2;",
+                "2:5: Expected next token to be IDENT, got ASSIGN instead",
+            ),
+            ("\n  )", "2:3: No prefix parse function for RPAREN found"),
+            (
+                "0b2",
+                "1:1: Could not parse 0b2 as an integer: invalid digit found in string",
+            ),
+        ];
+
+        for (input, expected) in tests {
+            let mut parser = Parser::new(Lexer::new(input));
+            parser.parse_program();
+
+            let errors = parser.errors();
+            if errors.first().map(|d| d.to_string()).as_deref() != Some(expected) {
+                panic!("Expected first error {:?}, got {:?}", expected, errors);
+            }
+        }
+    }
+
+    fn parse_errors(input: &str) -> Vec<String> {
+        let mut parser = Parser::new(Lexer::new(input));
+        parser.parse_program();
+
+        parser.errors().iter().map(|d| d.to_string()).collect()
+    }
+
+    #[test]
+    fn test_error_recovery() {
+        let tests: Vec<(&str, Vec<&str>)> = vec![
+            (
+                "let x 5; let = 10; let 838383;",
+                vec![
+                    "1:7: Expected next token to be ASSIGN, got INT instead",
+                    "1:14: Expected next token to be IDENT, got ASSIGN instead",
+                    "1:24: Expected next token to be IDENT, got INT instead",
+                ],
+            ),
+            (
+                "if (x { 1 }\nlet y = ;\nreturn );",
+                vec![
+                    "1:7: Expected next token to be RPAREN, got LBRACE instead",
+                    "2:9: No prefix parse function for SEMICOLON found",
+                    "3:8: No prefix parse function for RPAREN found",
+                ],
+            ),
+            (
+                "let f = fn(x) { let = 1; x };\nf(1;\nlet ok = 2;",
+                vec![
+                    "1:21: Expected next token to be IDENT, got ASSIGN instead",
+                    "2:4: Expected next token to be RPAREN, got SEMICOLON instead",
+                ],
+            ),
+            (
+                "if () { 1 }; let x = 2 let y = 3;",
+                vec!["1:5: No prefix parse function for RPAREN found"],
+            ),
+            // The hash's `{` was read before the error, but its `}` is
+            // still skipped along with it.
+            (
+                "{1: 2, 3 4}; let q = ;",
+                vec![
+                    "1:10: Expected next token to be COLON, got INT instead",
+                    "1:22: No prefix parse function for SEMICOLON found",
+                ],
+            ),
+            // An error on the `}` closing a block still closes it.
+            (
+                "let f = fn(x) { x + }; let y = 1; y",
+                vec!["1:21: No prefix parse function for RBRACE found"],
+            ),
+            (
+                "let g = fn() { let a = }; g",
+                vec!["1:24: No prefix parse function for RBRACE found"],
+            ),
+            (
+                "if (true) { 1 + } 5",
+                vec!["1:17: No prefix parse function for RBRACE found"],
+            ),
+            (
+                "let f = fn() { return 1 + }; let q = ;",
+                vec![
+                    "1:27: No prefix parse function for RBRACE found",
+                    "1:38: No prefix parse function for SEMICOLON found",
+                ],
+            ),
+            // The `else` branch belongs to the failed `if`.
+            (
+                "if (a { b } else { c };",
+                vec!["1:7: Expected next token to be RPAREN, got LBRACE instead"],
+            ),
+            (
+                "if (a { b } else if (c) { d } else { e }\nlet q = ;",
+                vec![
+                    "1:7: Expected next token to be RPAREN, got LBRACE instead",
+                    "2:9: No prefix parse function for SEMICOLON found",
+                ],
+            ),
+            (
+                "let f = fn() { let h = {1: [2}; 3 };\nlet q = ;",
+                vec![
+                    "1:30: Expected next token to be RBRACKET, got RBRACE instead",
+                    "2:9: No prefix parse function for SEMICOLON found",
+                ],
+            ),
+        ];
+
+        for (input, expected) in tests {
+            let errors = parse_errors(input);
+
+            if errors != expected {
+                panic!(
+                    "Wrong errors for {:?}\nexpected {:?}\ngot      {:?}",
+                    input, expected, errors
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_recovered_statements_are_kept() {
+        let mut parser = Parser::new(Lexer::new("let x 5; let y = 10; let = 1; return y;"));
+        let program = parser.parse_program();
+
+        if parser.errors().len() != 2 {
+            panic!("Expected 2 errors, got {:?}", parser.errors());
+        }
+
+        if program.string() != "let y = 10;return y;" {
+            panic!("Valid statements were lost, got {}", program.string());
+        }
+    }
+
+    #[test]
+    fn test_malformed_input_does_not_panic() {
+        let inputs = vec![
+            "if",
+            "if (",
+            "if (1",
+            "if (1)",
+            "if (1) {",
+            "if (1) { 2 } else",
+            "if (1) { 2 } else if",
+            "fn",
+            "fn(",
+            "fn(a,",
+            "fn(a, 1) {}",
+            "f(",
+            "f(1,",
+            "[1, 2",
+            "a[",
+            "a[1",
+            "{",
+            "{1",
+            "{1:",
+            "{1: 2,",
+            "let",
+            "let x",
+            "let x =",
+            "return +",
+            "-",
+            "!",
+            "1 +",
+            "(",
+            "(1",
+            "}}}",
+            ")))",
+            "\"unterminated",
+            "@ # $",
+            "let x = { let y = 1; };",
+        ];
+
+        for input in inputs {
+            let errors = parse_errors(input);
+
+            if errors.is_empty() {
+                panic!("Expected errors for {:?}", input);
+            }
+        }
+    }
+
+    #[test]
+    fn test_let_doc_comments() {
+        let input = "/// Cuenta los elementos.\n/// Devuelve un entero.\nlet contar = fn(xs) { len(xs) };\n\nlet x = 1;\n/// sin let\nx;\nlet y = 2;";
+        let program = create_parse_program(input);
+
+        let docs: Vec<Option<String>> = program
+            .statements
+            .iter()
+            .filter_map(|statement| match statement {
+                StatementVariant::Let(let_statement) => Some(let_statement.doc.clone()),
+                _ => None,
+            })
+            .collect();
+
+        let expected = vec![
+            Some("Cuenta los elementos.\nDevuelve un entero.".to_string()),
+            None,
+            None,
+        ];
+
+        if docs != expected {
+            panic!("Expected docs {:?}, got {:?}", expected, docs);
+        }
+    }
+
+    #[test]
+    fn test_unterminated_comment_error() {
+        let errors = parse_errors("let x = 1; /* sin cerrar");
+
+        if errors != vec!["1:12: Illegal token /*"] {
+            panic!("Expected an unterminated comment error, got {:?}", errors);
+        }
+    }
+
+    #[test]
+    fn test_float_literal_expression() {
+        let tests: Vec<(&str, f64, &str)> = vec![
+            ("2.75;", 2.75, "2.75"),
+            ("1e-9;", 1e-9, "1e-9"),
+            ("2.5E3;", 2500.0, "2.5E3"),
+        ];
+
+        for (input, value, string) in tests {
+            let program = create_parse_program(input);
+
+            match program.statements.first() {
+                Some(StatementVariant::Expression(ExpressionStatement {
+                    expression: Some(ExpressionVariants::Float(literal)),
+                    ..
+                })) => {
+                    if literal.value != value || literal.string() != string {
+                        panic!(
+                            "Expected {} ({}), got {} ({})",
+                            value,
+                            string,
+                            literal.value,
+                            literal.string()
+                        );
+                    }
+                }
+                other => panic!("Expected a FloatLiteral, got {:?}", other),
+            }
+        }
+
+        let errors = parse_errors("1e999");
+        if errors != vec!["1:1: Could not parse 1e999 as a float: number too large"] {
+            panic!("Expected an out of range error, got {:?}", errors);
+        }
+    }
+
+    #[test]
+    fn test_min_integer_literal() {
+        let program = create_parse_program("-9223372036854775808;");
+
+        match &program.statements[0] {
+            StatementVariant::Expression(ExpressionStatement {
+                expression: Some(ExpressionVariants::Integer(int_lit)),
+                ..
+            }) => {
+                if int_lit.value != i64::MIN || int_lit.token_literal() != "-9223372036854775808" {
+                    panic!("Wrong literal for i64::MIN, got {:?}", int_lit);
+                }
+            }
+            other => panic!("Expected an integer literal, got {:?}", other),
+        }
+
+        // Any other negative number is still a prefix expression.
+        let program = create_parse_program("-9223372036854775807;");
+        if program.string() != "(-9223372036854775807)" {
+            panic!("Expected a prefix expression, got {}", program.string());
+        }
+    }
+
+    #[test]
+    fn test_integer_literal_errors() {
+        let tests: Vec<(&str, &str)> = vec![
+            (
+                "0b102",
+                "1:1: Could not parse 0b102 as an integer: invalid digit found in string",
+            ),
+            (
+                "0x",
+                "1:1: Could not parse 0x as an integer: cannot parse integer from empty string",
+            ),
+            (
+                "0xFG",
+                "1:1: Could not parse 0xFG as an integer: invalid digit found in string",
+            ),
+        ];
+
+        // With the `bigint` feature large literals are accepted.
+        #[cfg(not(feature = "bigint"))]
+        let tests = [
+            tests,
+            vec![
+                (
+                    "99999999999999999999",
+                    "1:1: Could not parse 99999999999999999999 as an integer: number too large to fit in target type",
+                ),
+                (
+                    "0x8000000000000000",
+                    "1:1: Could not parse 0x8000000000000000 as an integer: number too large to fit in target type",
+                ),
+                // `**` binds tighter than `-`, so the digits stand alone.
+                (
+                    "-9223372036854775808 ** 2",
+                    "1:2: Could not parse 9223372036854775808 as an integer: number too large to fit in target type",
+                ),
+            ],
+        ]
+        .concat();
+
+        for (input, expected) in tests {
+            let errors = parse_errors(input);
+
+            if errors != vec![expected] {
+                panic!("Expected {:?}, got {:?}", expected, errors);
+            }
+        }
+    }
 }
@@ -0,0 +1,204 @@
+#[cfg(test)]
+mod tests {
+    use mono::repl::{complete, is_incomplete, Repl};
+
+    fn run(inputs: &[&str]) -> Vec<String> {
+        colored::control::set_override(false);
+
+        let mut repl = Repl::new();
+        inputs.iter().map(|input| repl.eval(input)).collect()
+    }
+
+    #[test]
+    fn test_state_persists() {
+        let outputs = run(&[
+            "let a = 5;",
+            "a * 2",
+            "let add = fn(x) { x + a };",
+            "add(1)",
+            "let a = 10;",
+            "add(1)",
+            "[a, \"a\"]",
+        ]);
+
+        let expected = vec!["", "10\n", "", "6\n", "", "11\n", "[10, \"a\"]\n"];
+
+        if outputs != expected {
+            panic!("Expected {:?}, got {:?}", expected, outputs);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let outputs = run(&["let x 1", "missing + 1", "let y = 2;", "y"]);
+
+        let expected = vec![
+            "\
+error[E0001]: Expected next token to be ASSIGN, got INT instead
+ --> <repl>:1:7
+  |
+1 | let x 1
+  |       ^ expected ASSIGN here
+",
+            "\
+error[E0100]: identifier not found: missing
+ --> <repl>:1:1
+  |
+1 | missing + 1
+  | ^^^^^^^
+",
+            "",
+            "2\n",
+        ];
+
+        if outputs != expected {
+            panic!("Expected {:?}, got {:?}", expected, outputs);
+        }
+    }
+
+    #[test]
+    fn test_error_in_earlier_input() {
+        // The error is inside a function from the first input, so that
+        // is the text shown, not the call.
+        let outputs = run(&[
+            "let f = fn(x) {\n  let y = 2;\n  x / 0\n};\n",
+            "let = 1;",
+            "f(1)",
+        ]);
+        let expected = "\
+error[E0100]: division by zero: 1 / 0
+ --> <repl>:3:3
+  |
+3 |   x / 0
+  |   ^^^^^
+";
+
+        if outputs[2] != expected {
+            panic!("Expected {:?}, got {:?}", expected, outputs[2]);
+        }
+    }
+
+    #[test]
+    fn test_failed_input_keeps_state() {
+        // A line with parse errors is not evaluated at all.
+        let outputs = run(&["let a = 1;", "let a = 2; let = 3;", "a"]);
+
+        if outputs[2] != "1\n" {
+            panic!("Expected a to still be 1, got {:?}", outputs[2]);
+        }
+    }
+
+    #[test]
+    fn test_is_incomplete() {
+        let tests = vec![
+            ("let x = 5;\n", false),
+            ("let f = fn(x) {\n", true),
+            ("let f = fn(x) {\n  x * 2\n};\n", false),
+            ("add(1,\n", true),
+            ("[1, 2,\n", true),
+            ("\"unterminated\n", true),
+            ("/* open comment\n", true),
+            ("/* closed */ 1\n", false),
+            // Extra closing brackets are left for the parser to report.
+            ("}\n", false),
+            ("\"\\q\"\n", false),
+        ];
+
+        for (input, expected) in tests {
+            if is_incomplete(input) != expected {
+                panic!("is_incomplete({:?}) should be {}", input, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_commands() {
+        let outputs = run(&[
+            ":tokens x;",
+            ":ast -a",
+            "let b = 2; let a = [1];",
+            ":env",
+            ":type a",
+            ":type 1 / 0",
+            ":reset",
+            ":env",
+            ":nope",
+            ":tokens",
+        ]);
+        let expected = vec![
+            "1:1 IDENT \"x\"\n1:2 SEMICOLON \";\"\n1:3 EOF \" \"\n",
+            "Prefix - @ 1:1\n  Identifier a @ 1:2\n",
+            "",
+            "a = [1]\nb = 2\n",
+            "ARRAY\n",
+            "\
+error[E0100]: division by zero: 1 / 0
+ --> <repl>:1:1
+  |
+1 | 1 / 0
+  | ^^^^^
+",
+            "",
+            "",
+            "unknown command :nope, see :help\n",
+            "wrong arguments for :tokens, see :help\n",
+        ];
+
+        if outputs != expected {
+            panic!("Expected {:?}, got {:?}", expected, outputs);
+        }
+    }
+
+    #[test]
+    fn test_load_command() {
+        let path = std::env::temp_dir().join(format!("mono_repl_load_{}.mono", std::process::id()));
+        std::fs::write(&path, "let double = fn(x) { x * 2 };\ndouble(4)\n").unwrap();
+
+        let load = format!(":load {}", path.display());
+        let outputs = run(&[&load, "double(5)", ":time double(6)"]);
+        std::fs::remove_file(&path).unwrap();
+
+        if outputs[0] != "8\n" || outputs[1] != "10\n" {
+            panic!(
+                "Expected the script to run in the session, got {:?}",
+                outputs
+            );
+        }
+        if !outputs[2].starts_with("12\ntook ") {
+            panic!("Expected a timed result, got {:?}", outputs[2]);
+        }
+    }
+
+    #[test]
+    fn test_complete() {
+        let mut repl = Repl::new();
+        repl.eval("let length = 3; let person = {\"name\": 1, \"nick\": 2, 3: 4};");
+        let env = repl.environment();
+
+        let tests = vec![
+            ("le", (0, vec!["len", "length", "let"])),
+            ("1 + fa", (4, vec!["false"])),
+            ("pu", (0, vec!["push", "puts"])),
+            ("per", (0, vec!["person"])),
+            ("person[\"n", (7, vec!["\"name\"", "\"nick\""])),
+            ("person[", (7, vec!["3", "\"name\"", "\"nick\""])),
+            ("person[le", (7, vec!["len", "length", "let"])),
+            ("length[le", (7, vec!["len", "length", "let"])),
+            ("\"le", (3, vec![])),
+            ("", (0, vec![])),
+            ("1", (1, vec![])),
+        ];
+
+        for (line, (start, candidates)) in tests {
+            let expected = (start, candidates.iter().map(|c| c.to_string()).collect());
+            let got = complete(env, line, line.len());
+
+            if got != expected {
+                panic!(
+                    "complete({:?}) should be {:?}, got {:?}",
+                    line, expected, got
+                );
+            }
+        }
+    }
+}